@@ -0,0 +1,14 @@
+use diesel_core::square::NUM_SQUARES;
+use diesel_core::tables::{DISTANCE, MANHATTAN_DISTANCE};
+
+use crate::output::{Element, Storage, Table, TableWriter};
+
+pub fn write_distances(w: &mut TableWriter) {
+    write_distance_table(w, "DISTANCE", &DISTANCE);
+    write_distance_table(w, "MANHATTAN_DISTANCE", &MANHATTAN_DISTANCE);
+}
+
+fn write_distance_table(w: &mut TableWriter, name: &str, distances: &[[u8; NUM_SQUARES]; NUM_SQUARES]) {
+    let values: Vec<u64> = distances.iter().flatten().map(|distance| *distance as u64).collect();
+    w.write_table(&Table::new(name, Storage::Static, Element::U8, &[NUM_SQUARES, NUM_SQUARES], values));
+}
@@ -1,6 +1,8 @@
 use std::env;
-use std::fs::File;
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 mod constants;
 mod king;
@@ -8,53 +10,232 @@ mod knight;
 mod pawn;
 mod bishop;
 mod rook;
+mod rays;
+mod distance;
 mod magic;
+mod prng;
+mod zobrist;
+pub mod output;
 
-use crate::constants::{generate_constants, write_constants};
+use crate::constants::write_constants;
 
-use crate::king::{generate_king_moves, write_king_moves};
-use crate::knight::{generate_knight_moves, write_knight_moves};
-use crate::pawn::{generate_pawn_moves, generate_pawn_attacks, write_pawn_moves, write_pawn_attacks};
+use crate::king::write_king_moves;
+use crate::knight::write_knight_moves;
+use crate::pawn::{write_pawn_moves, write_pawn_attacks};
 
-use crate::rook::{generate_rook_rays, write_rook_rays};
-use crate::bishop::{generate_bishop_rays, write_bishop_rays};
+use crate::rook::write_rook_rays;
+use crate::bishop::write_bishop_rays;
 
-use crate::magic::{generate_magics, write_magics};
+use crate::rays::{write_rays, write_between_and_line};
+use crate::distance::write_distances;
+
+use crate::magic::{generate_magics, write_magics, write_magic_tables};
+use crate::zobrist::{generate_zobrist_keys, write_zobrist_keys};
+
+use crate::output::{OutputFormat, TableWriter};
+
+pub use crate::zobrist::ZobristKeys;
+
+pub const DEFAULT_SEED: u64 = 0x5EED_D1E5_E1C0_FFEE;
+
+/// The copy of `gen.rs` that diesel-core includes, generated with the default options.
+pub const COMMITTED_TABLES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../diesel-core/src/tables/gen.rs");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableSet {
+    Constants,
+    King,
+    Knight,
+    Pawn,
+    RookRays,
+    BishopRays,
+    Rays,
+    Distance,
+    Magics,
+    MagicTables,
+    Zobrist,
+}
+
+pub const ALL_TABLE_SETS: [TableSet; 11] = [
+    TableSet::Constants,
+    TableSet::King,
+    TableSet::Knight,
+    TableSet::Pawn,
+    TableSet::RookRays,
+    TableSet::BishopRays,
+    TableSet::Rays,
+    TableSet::Distance,
+    TableSet::Magics,
+    TableSet::MagicTables,
+    TableSet::Zobrist,
+];
+
+/// The tables diesel-core includes from `gen.rs`. Everything else is computed there by `const fn`.
+pub const COMMITTED_TABLE_SETS: [TableSet; 2] = [TableSet::Magics, TableSet::Zobrist];
+
+impl FromStr for TableSet {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "constants" => Ok(TableSet::Constants),
+            "king" => Ok(TableSet::King),
+            "knight" => Ok(TableSet::Knight),
+            "pawn" => Ok(TableSet::Pawn),
+            "rook-rays" => Ok(TableSet::RookRays),
+            "bishop-rays" => Ok(TableSet::BishopRays),
+            "rays" => Ok(TableSet::Rays),
+            "distance" => Ok(TableSet::Distance),
+            "magics" => Ok(TableSet::Magics),
+            "magic-tables" => Ok(TableSet::MagicTables),
+            "zobrist" => Ok(TableSet::Zobrist),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct GenOptions {
+    pub out_dir: PathBuf,
+    pub tables: Vec<TableSet>,
+    pub seed: u64,
+    pub zobrist_keys: ZobristKeys,
+    pub format: OutputFormat,
+}
+
+impl Default for GenOptions {
+    fn default() -> Self {
+        Self {
+            out_dir: PathBuf::from(env::var("OUT_DIR").unwrap_or("./out".to_string())),
+            tables: COMMITTED_TABLE_SETS.to_vec(),
+            seed: DEFAULT_SEED,
+            zobrist_keys: ZobristKeys::Polyglot,
+            format: OutputFormat::Rust,
+        }
+    }
+}
 
 pub fn generate_all_tables() {
-    // Generate constants
-    generate_constants();
+    generate_tables(&GenOptions { tables: ALL_TABLE_SETS.to_vec(), ..GenOptions::default() }).unwrap();
+}
+
+/// Generates the requested tables and writes them to `gen.<ext>` in the output directory.
+/// The magic attack tables are large, so they are kept in a separate `magic_tables.<ext>`.
+pub fn generate_tables(options: &GenOptions) -> io::Result<()> {
+    let (tables, magic_tables) = render_tables(options);
+
+    fs::create_dir_all(&options.out_dir)?;
+    let extension = options.format.extension();
+    fs::write(options.out_dir.join(format!("gen.{}", extension)), tables)?;
+    if let Some(magic_tables) = magic_tables {
+        fs::write(options.out_dir.join(format!("magic_tables.{}", extension)), magic_tables)?;
+    }
+    Ok(())
+}
+
+/// Regenerates `gen.<ext>` and compares it against `committed`, returning a description of each
+/// differing line. An empty result means the committed copy is up to date.
+pub fn check_tables(options: &GenOptions, committed: &Path) -> io::Result<Vec<String>> {
+    let (tables, _) = render_tables(options);
+    let committed = fs::read(committed)?;
+
+    if options.format == OutputFormat::Binary {
+        return Ok(if tables == committed { Vec::new() } else { vec!["binary tables differ".to_string()] });
+    }
+
+    let generated = String::from_utf8_lossy(&tables);
+    let committed = String::from_utf8_lossy(&committed);
+    let generated_lines: Vec<&str> = generated.lines().collect();
+    let committed_lines: Vec<&str> = committed.lines().collect();
 
-    // Generate
-    generate_king_moves();
-    generate_knight_moves();
-    generate_pawn_moves();
-    generate_pawn_attacks();
+    let mut differences = Vec::new();
+    for i in 0..generated_lines.len().max(committed_lines.len()) {
+        let generated_line = generated_lines.get(i).copied().unwrap_or("");
+        let committed_line = committed_lines.get(i).copied().unwrap_or("");
+        if generated_line != committed_line {
+            differences.push(format!(
+                "line {}:\n- {}\n+ {}",
+                i + 1,
+                truncate(committed_line),
+                truncate(generated_line)
+            ));
+        }
+    }
+    Ok(differences)
+}
 
-    // Generate sliding piece rays
-    generate_rook_rays();
-    generate_bishop_rays();
+fn truncate(line: &str) -> &str {
+    match line.char_indices().nth(120) {
+        Some((end, _)) => &line[..end],
+        None => line,
+    }
+}
 
-    // Generate magics
-    generate_magics();
+/// Generates the requested tables, returning the contents of the `gen` file and, if the magic
+/// attack tables were requested, the `magic_tables` file.
+pub fn render_tables(options: &GenOptions) -> (Vec<u8>, Option<Vec<u8>>) {
+    let wants = |set: TableSet| options.tables.contains(&set);
+    let wants_magics = wants(TableSet::Magics) || wants(TableSet::MagicTables);
 
-    let out_dir: String = env::var("OUT_DIR").unwrap_or("./out".to_string());
-    let gen_path: PathBuf = Path::new(&out_dir).join("gen.rs");
-    let f: &mut File = &mut File::create(gen_path).unwrap();
+    // Only the magic search and the zobrist keys need generating, everything else comes from diesel-core
+    let magics = wants_magics.then(|| generate_magics(options.seed));
+    let zobrist_keys = wants(TableSet::Zobrist).then(|| generate_zobrist_keys(options.zobrist_keys, options.seed));
+
+    let mut tables: Vec<u8> = Vec::new();
+    let mut writer = TableWriter::new(options.format, &mut tables);
+    let w = &mut writer;
 
     // Write constants
-    write_constants(f);
+    if wants(TableSet::Constants) {
+        write_constants(w);
+    }
 
     // Write
-    write_knight_moves(f);
-    write_king_moves(f);
-    write_pawn_moves(f);
-    write_pawn_attacks(f);
+    if wants(TableSet::Knight) {
+        write_knight_moves(w);
+    }
+    if wants(TableSet::King) {
+        write_king_moves(w);
+    }
+    if wants(TableSet::Pawn) {
+        write_pawn_moves(w);
+        write_pawn_attacks(w);
+    }
 
     // Write sliding piece rays
-    write_rook_rays(f);
-    write_bishop_rays(f);
+    if wants(TableSet::RookRays) {
+        write_rook_rays(w);
+    }
+    if wants(TableSet::BishopRays) {
+        write_bishop_rays(w);
+    }
+
+    // Write geometry
+    if wants(TableSet::Rays) {
+        write_rays(w);
+        write_between_and_line(w);
+    }
+    if wants(TableSet::Distance) {
+        write_distances(w);
+    }
 
     // Write magics
-    write_magics(f);
-}
\ No newline at end of file
+    if let Some(magics) = magics.as_ref().filter(|_| wants(TableSet::Magics)) {
+        write_magics(w, magics);
+    }
+
+    // Write zobrist keys
+    if let Some(zobrist_keys) = &zobrist_keys {
+        write_zobrist_keys(w, zobrist_keys);
+    }
+    writer.finish();
+
+    let magic_tables = magics.as_ref().filter(|_| wants(TableSet::MagicTables)).map(|magics| {
+        let mut magic_tables: Vec<u8> = Vec::new();
+        let mut writer = TableWriter::new(options.format, &mut magic_tables);
+        write_magic_tables(&mut writer, magics);
+        writer.finish();
+        magic_tables
+    });
+
+    (tables, magic_tables)
+}
@@ -1,29 +1,14 @@
-use std::fs::File;
-use std::io::Write;
-use rand::Rng;
-
-use rand::rngs::ThreadRng;
-
 use diesel_core::bitboard::{BitBoard, EMPTY_BITBOARD};
-use diesel_core::magic::{Magic};
+use diesel_core::magic::{Magic, PEXT_ENABLED};
 use diesel_core::piece::Piece;
 use diesel_core::rank::Rank;
 use diesel_core::square::{ALL_SQUARES, NUM_SQUARES, Square};
-use crate::bishop::get_bishop_ray;
-use crate::rook::get_rook_ray;
+use diesel_core::tables::{BISHOP_RAYS, ROOK_RAYS};
+use crate::output::{Element, Storage, Table, TableWriter};
+use crate::prng::Prng;
 
-// static mut SLIDING_PIECE_ATTACKS: [BitBoard; todo!()] = [EMPTY_BITBOARD; todo!()];
-
-static mut ROOK_MAGICS: [Magic; NUM_SQUARES] = [Magic{
-    mask: EMPTY_BITBOARD,
-    magic: 0,
-    shift: 0,
-}; NUM_SQUARES];
-static mut BISHOP_MAGICS: [Magic; NUM_SQUARES] = [Magic{
-    mask: EMPTY_BITBOARD,
-    magic: 0,
-    shift: 0,
-}; NUM_SQUARES];
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
 
 const ROOK_RELEVANT_INDEX_BITS: [u8; NUM_SQUARES] = [
     12, 11, 11, 11, 11, 11, 11, 12,
@@ -47,15 +32,28 @@ const BISHOP_RELEVANT_INDEX_BITS: [u8; NUM_SQUARES] = [
     6, 5, 5, 5, 5, 5, 5, 6
 ];
 
-pub fn generate_magics() {
-    for piece in [Piece::Rook, Piece::Bishop] {
-        for square in ALL_SQUARES {
-            generate_magic(piece, square);
-        }
-    }
+/// The magic entries found for every square, for both sliding pieces.
+pub struct Magics {
+    pub rook: [Magic; NUM_SQUARES],
+    pub bishop: [Magic; NUM_SQUARES],
 }
 
-fn generate_magic(piece: Piece, square: Square) {
+pub fn generate_magics(seed: u64) -> Magics {
+    let mut prng = Prng::new(seed);
+    let mut generate_piece_magics = |piece: Piece| {
+        let mut offset: usize = 0;
+        ALL_SQUARES.map(|square| {
+            let magic = generate_magic(piece, square, offset, &mut prng);
+            offset += 1 << (64 - magic.shift);
+            magic
+        })
+    };
+    let rook = generate_piece_magics(Piece::Rook);
+    let bishop = generate_piece_magics(Piece::Bishop);
+    Magics { rook, bishop }
+}
+
+fn generate_magic(piece: Piece, square: Square, offset: usize, prng: &mut Prng) -> Magic {
     let magic_mask: BitBoard = match piece {
         Piece::Rook => generate_magic_rook_mask(square),
         Piece::Bishop => generate_magic_bishop_mask(square),
@@ -68,30 +66,23 @@ fn generate_magic(piece: Piece, square: Square) {
     };
     let shift: u8 = 64 - index_bits;
 
-    let mut rng: ThreadRng = rand::thread_rng();
-
+    // Always search for a magic number, so the committed magics work whether or not PEXT is enabled
     loop {
-        let magic_number: u64 = rng.gen::<u64>() & rng.gen::<u64>() & rng.gen::<u64>();
+        let magic_number: u64 = prng.next_u64() & prng.next_u64() & prng.next_u64();
         let magic = Magic {
             mask: magic_mask,
             magic: magic_number,
             shift,
+            offset,
         };
-        if let Ok(_table) = try_make_table(piece, square, magic) {
-            unsafe {
-                match piece {
-                    Piece::Rook => ROOK_MAGICS[square.to_index()] = magic,
-                    Piece::Bishop => BISHOP_MAGICS[square.to_index()] = magic,
-                    _ => panic!("Invalid piece"),
-                }
-            }
-            return;
+        if try_make_table(piece, square, magic, Magic::get_magic_index).is_ok() {
+            return magic;
         }
     }
 }
 
 fn generate_magic_rook_mask(square: Square) -> BitBoard {
-    get_rook_ray(square) & !ALL_SQUARES
+    ROOK_RAYS[square.to_index()] & !ALL_SQUARES
         .iter()
         .filter(|edge| {
             (square.get_rank() == edge.get_rank()
@@ -103,7 +94,7 @@ fn generate_magic_rook_mask(square: Square) -> BitBoard {
 }
 
 fn generate_magic_bishop_mask(square: Square) -> BitBoard {
-    get_bishop_ray(square) & !ALL_SQUARES
+    BISHOP_RAYS[square.to_index()] & !ALL_SQUARES
         .iter()
         .filter(|sq| {
             sq.get_rank() == Rank::First
@@ -114,19 +105,34 @@ fn generate_magic_bishop_mask(square: Square) -> BitBoard {
         .fold(EMPTY_BITBOARD, |b, s| b | BitBoard::from_square(*s))
 }
 
-fn try_make_table(piece: Piece, square: Square, magic: Magic) -> Result<Vec<BitBoard>, ()> {
+fn generate_sliding_attacks(square: Square, blockers: BitBoard, directions: &[(i32, i32)]) -> BitBoard {
+    let mut attacks = EMPTY_BITBOARD;
+    for &(file_offset, rank_offset) in directions {
+        let mut current = square;
+        while let Some(next) = current.offset(file_offset, rank_offset) {
+            attacks.set_bit(next);
+            if !(blockers & BitBoard::from_square(next)).is_empty() {
+                break;
+            }
+            current = next;
+        }
+    }
+    attacks
+}
+
+fn try_make_table(piece: Piece, square: Square, magic: Magic, get_index: fn(&Magic, BitBoard) -> usize) -> Result<Vec<BitBoard>, ()> {
     let index_bits = 64 - magic.shift;
     let mut table = vec![EMPTY_BITBOARD; 1 << index_bits];
 
     let mut blockers = EMPTY_BITBOARD;
     loop {
         let attack: BitBoard = match piece {
-            Piece::Rook => todo!(),
-            Piece::Bishop => todo!(),
+            Piece::Rook => generate_sliding_attacks(square, blockers, &ROOK_DIRECTIONS),
+            Piece::Bishop => generate_sliding_attacks(square, blockers, &BISHOP_DIRECTIONS),
             _ => panic!("Invalid piece"),
         };
-        let table_entry = &mut table[magic.get_magic_index(blockers)];
-        if table_entry.is_empty() {
+        let table_entry = &mut table[get_index(&magic, blockers)];
+        if (*table_entry).is_empty() {
             *table_entry = attack;
         } else if *table_entry != attack {
             return Err(());
@@ -139,8 +145,34 @@ fn try_make_table(piece: Piece, square: Square, magic: Magic) -> Result<Vec<BitB
     Ok(table)
 }
 
-pub fn write_magics(f: &mut File) {
-    todo!()
+pub fn write_magics(w: &mut TableWriter, magics: &Magics) {
+    write_magic_entries(w, "ROOK", &magics.rook);
+    write_magic_entries(w, "BISHOP", &magics.bishop);
+}
+
+// The layout of the attack tables depends on how they are indexed, so record it alongside them
+pub fn write_magic_tables(w: &mut TableWriter, magics: &Magics) {
+    w.write_table(&Table::new("PEXT_INDEXED", Storage::Const, Element::Bool, &[], vec![PEXT_ENABLED as u64]));
+    write_magic_table(w, Piece::Rook, "ROOK", &magics.rook);
+    write_magic_table(w, Piece::Bishop, "BISHOP", &magics.bishop);
+}
+
+fn get_table_size(magics: &[Magic; NUM_SQUARES]) -> usize {
+    magics.iter().map(|magic| 1usize << (64 - magic.shift)).sum()
+}
+
+fn write_magic_entries(w: &mut TableWriter, name: &str, magics: &[Magic; NUM_SQUARES]) {
+    let table_size = get_table_size(magics) as u64;
+    w.write_table(&Table::magics(&format!("{}_MAGICS", name), magics));
+    w.write_table(&Table::new(&format!("{}_TABLE_SIZE", name), Storage::Const, Element::Usize, &[], vec![table_size]));
+}
+
+fn write_magic_table(w: &mut TableWriter, piece: Piece, name: &str, magics: &[Magic; NUM_SQUARES]) {
+    let attacks: Vec<BitBoard> = ALL_SQUARES
+        .iter()
+        .flat_map(|square| try_make_table(piece, *square, magics[square.to_index()], Magic::get_index).unwrap())
+        .collect();
+    w.write_table(&Table::bitboards(&format!("{}_MOVES", name), Storage::Static, &[get_table_size(magics)], &attacks));
 }
 
 
@@ -0,0 +1,111 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use diesel_gen::output::OutputFormat;
+use diesel_gen::{check_tables, generate_tables, GenOptions, TableSet, ZobristKeys, COMMITTED_TABLES};
+
+const USAGE: &str = "Usage: diesel-gen [OPTIONS]
+
+Options:
+  --out-dir <DIR>     Directory to write the tables to (default: $OUT_DIR or ./out)
+  --tables <LIST>     Comma-separated subset of constants, king, knight, pawn, rook-rays,
+                      bishop-rays, rays, distance, magics, magic-tables, zobrist
+                      (default: magics,zobrist, the tables diesel-core includes)
+  --seed <SEED>       Seed for the magic search and random zobrist keys, decimal or 0x-prefixed hex
+  --format <FORMAT>   rust, binary or json (default: rust)
+  --random-zobrist    Emit seeded zobrist keys instead of the Polyglot ones
+  --check [FILE]      Regenerate the tables and diff them against FILE instead of writing them
+                      (default: the copy committed in diesel-core)
+  -h, --help          Print this message";
+
+struct Args {
+    options: GenOptions,
+    check: Option<PathBuf>,
+}
+
+fn parse_seed(s: &str) -> Result<u64, String> {
+    let parsed = match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(&hex.replace('_', ""), 16),
+        None => s.replace('_', "").parse::<u64>(),
+    };
+    parsed.map_err(|_| format!("invalid seed '{}'", s))
+}
+
+fn parse_args(args: &[String]) -> Result<Option<Args>, String> {
+    let mut options = GenOptions::default();
+    let mut check = None;
+
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} requires a value", name));
+        match arg.as_str() {
+            "--out-dir" => options.out_dir = PathBuf::from(value(arg)?),
+            "--tables" => {
+                options.tables = value(arg)?
+                    .split(',')
+                    .map(|name| name.trim().parse::<TableSet>().map_err(|_| format!("unknown table '{}'", name)))
+                    .collect::<Result<Vec<TableSet>, String>>()?;
+            }
+            "--seed" => options.seed = parse_seed(value(arg)?)?,
+            "--format" => {
+                let format = value(arg)?;
+                options.format = format.parse::<OutputFormat>().map_err(|_| format!("unknown format '{}'", format))?;
+            }
+            "--random-zobrist" => options.zobrist_keys = ZobristKeys::Random,
+            "--check" => {
+                let file = match args.peek() {
+                    Some(file) if !file.starts_with("--") => PathBuf::from(args.next().unwrap()),
+                    _ => PathBuf::from(COMMITTED_TABLES),
+                };
+                check = Some(file);
+            }
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(Some(Args { options, check }))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match parse_args(&args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match args.check {
+        Some(committed) => match check_tables(&args.options, &committed) {
+            Ok(differences) if differences.is_empty() => {
+                println!("{} is up to date", committed.display());
+                ExitCode::SUCCESS
+            }
+            Ok(differences) => {
+                for difference in &differences {
+                    println!("{}", difference);
+                }
+                eprintln!("{} differs from the generated tables on {} line(s)", committed.display(), differences.len());
+                ExitCode::FAILURE
+            }
+            Err(error) => {
+                eprintln!("error: could not read {}: {}", committed.display(), error);
+                ExitCode::FAILURE
+            }
+        },
+        None => match generate_tables(&args.options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: could not write tables to {}: {}", args.options.out_dir.display(), error);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use diesel_core::bitboard::BitBoard;
+use diesel_core::magic::Magic;
+
+/// Identifies binary table blobs, followed by a little-endian `u16` format version.
+pub const BINARY_MAGIC: &[u8; 4] = b"DSLT";
+pub const BINARY_VERSION: u16 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Rust,
+    Binary,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Rust => "rs",
+            OutputFormat::Binary => "bin",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(OutputFormat::Rust),
+            "binary" => Ok(OutputFormat::Binary),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Storage {
+    Const,
+    Static,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Element {
+    BitBoard,
+    U64,
+    U8,
+    Usize,
+    Bool,
+    /// Stored as four values: mask, magic, shift and offset
+    Magic,
+}
+
+impl Element {
+    fn to_code(self) -> u8 {
+        self as u8
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        [Element::BitBoard, Element::U64, Element::U8, Element::Usize, Element::Bool, Element::Magic]
+            .into_iter()
+            .find(|element| element.to_code() == code)
+    }
+
+    fn values_per_element(self) -> usize {
+        match self {
+            Element::Magic => 4,
+            _ => 1,
+        }
+    }
+
+    fn byte_width(self) -> usize {
+        match self {
+            Element::U8 | Element::Bool => 1,
+            _ => 8,
+        }
+    }
+
+    fn rust_type(self) -> &'static str {
+        match self {
+            Element::BitBoard => "BitBoard",
+            Element::U64 => "u64",
+            Element::U8 => "u8",
+            Element::Usize => "usize",
+            Element::Bool => "bool",
+            Element::Magic => "Magic",
+        }
+    }
+}
+
+/// A generated table, flattened in row-major order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    pub name: String,
+    pub storage: Storage,
+    pub element: Element,
+    /// The array dimensions, outermost first. Empty for a single value.
+    pub shape: Vec<usize>,
+    pub values: Vec<u64>,
+}
+
+impl Table {
+    pub fn new(name: &str, storage: Storage, element: Element, shape: &[usize], values: Vec<u64>) -> Self {
+        let len: usize = shape.iter().product::<usize>() * element.values_per_element();
+        assert_eq!(values.len(), len, "{} has the wrong number of values", name);
+        Self {
+            name: name.to_string(),
+            storage,
+            element,
+            shape: shape.to_vec(),
+            values,
+        }
+    }
+
+    pub fn bitboards<'a>(name: &str, storage: Storage, shape: &[usize], bitboards: impl IntoIterator<Item = &'a BitBoard>) -> Self {
+        Self::new(name, storage, Element::BitBoard, shape, bitboards.into_iter().map(|bitboard| bitboard.0).collect())
+    }
+
+    pub fn magics<'a>(name: &str, magics: impl IntoIterator<Item = &'a Magic>) -> Self {
+        let values: Vec<u64> = magics
+            .into_iter()
+            .flat_map(|magic| [magic.mask.0, magic.magic, magic.shift as u64, magic.offset as u64])
+            .collect();
+        let len = values.len() / Element::Magic.values_per_element();
+        Self::new(name, Storage::Const, Element::Magic, &[len], values)
+    }
+
+    fn rust_type(&self) -> String {
+        self.shape
+            .iter()
+            .rev()
+            .fold(self.element.rust_type().to_string(), |inner, len| format!("[{}; {}]", inner, len))
+    }
+
+    fn rust_value(&self, values: &[u64]) -> String {
+        match self.element {
+            Element::BitBoard => format!("BitBoard({})", values[0]),
+            Element::U64 => format!("0x{:016X}", values[0]),
+            Element::U8 | Element::Usize => values[0].to_string(),
+            Element::Bool => (values[0] != 0).to_string(),
+            Element::Magic => format!(
+                "Magic {{ mask: BitBoard({}), magic: {}, shift: {}, offset: {} }}",
+                values[0], values[1], values[2], values[3]
+            ),
+        }
+    }
+
+    fn json_value(&self, values: &[u64]) -> String {
+        // 64-bit values are written as hex strings, as JSON numbers lose precision above 2^53
+        match self.element {
+            Element::BitBoard | Element::U64 => format!("\"0x{:016X}\"", values[0]),
+            Element::U8 | Element::Usize => values[0].to_string(),
+            Element::Bool => (values[0] != 0).to_string(),
+            Element::Magic => format!(
+                "{{\"mask\": \"0x{:016X}\", \"magic\": \"0x{:016X}\", \"shift\": {}, \"offset\": {}}}",
+                values[0], values[1], values[2], values[3]
+            ),
+        }
+    }
+
+    fn element_strings(&self, value: fn(&Table, &[u64]) -> String) -> Vec<String> {
+        self.values
+            .chunks(self.element.values_per_element())
+            .map(|values| value(self, values))
+            .collect()
+    }
+}
+
+/// Writes tables in one of the supported output formats.
+pub struct TableWriter<'a> {
+    format: OutputFormat,
+    out: &'a mut dyn Write,
+    tables_written: usize,
+}
+
+impl<'a> TableWriter<'a> {
+    pub fn new(format: OutputFormat, out: &'a mut dyn Write) -> Self {
+        match format {
+            OutputFormat::Rust => {}
+            OutputFormat::Binary => {
+                out.write_all(BINARY_MAGIC).unwrap();
+                out.write_all(&BINARY_VERSION.to_le_bytes()).unwrap();
+            }
+            OutputFormat::Json => writeln!(out, "{{").unwrap(),
+        }
+        Self { format, out, tables_written: 0 }
+    }
+
+    pub fn write_table(&mut self, table: &Table) {
+        match self.format {
+            OutputFormat::Rust => self.write_rust(table),
+            OutputFormat::Binary => self.write_binary(table),
+            OutputFormat::Json => self.write_json(table),
+        }
+        self.tables_written += 1;
+    }
+
+    pub fn finish(self) {
+        if self.format == OutputFormat::Json {
+            if self.tables_written > 0 {
+                writeln!(self.out).unwrap();
+            }
+            writeln!(self.out, "}}").unwrap();
+        }
+    }
+
+    fn write_rust(&mut self, table: &Table) {
+        let storage = match table.storage {
+            Storage::Const => "const",
+            Storage::Static => "static",
+        };
+        let declaration = format!("pub {} {}: {}", storage, table.name, table.rust_type());
+        let elements = table.element_strings(Table::rust_value);
+
+        if table.shape.is_empty() {
+            writeln!(self.out, "{} = {};", declaration, elements[0]).unwrap();
+            return;
+        }
+
+        if table.shape.len() == 1 && !matches!(table.element, Element::BitBoard | Element::Magic) {
+            writeln!(self.out, "{} = [{}];", declaration, elements.join(", ")).unwrap();
+            return;
+        }
+
+        writeln!(self.out, "{} = [", declaration).unwrap();
+        if table.shape.len() == 1 {
+            for element in elements {
+                writeln!(self.out, "    {},", element).unwrap();
+            }
+        } else {
+            write_nested(self.out, &table.shape, &elements, 1, true);
+        }
+        writeln!(self.out, "];").unwrap();
+    }
+
+    fn write_json(&mut self, table: &Table) {
+        if self.tables_written > 0 {
+            writeln!(self.out, ",").unwrap();
+        }
+        let elements = table.element_strings(Table::json_value);
+
+        if table.shape.is_empty() {
+            write!(self.out, "  \"{}\": {}", table.name, elements[0]).unwrap();
+            return;
+        }
+
+        writeln!(self.out, "  \"{}\": [", table.name).unwrap();
+        if table.shape.len() == 1 {
+            let rows: Vec<String> = elements.iter().map(|element| format!("    {}", element)).collect();
+            writeln!(self.out, "{}", rows.join(",\n")).unwrap();
+        } else {
+            write_nested(self.out, &table.shape, &elements, 2, false);
+        }
+        write!(self.out, "  ]").unwrap();
+    }
+
+    fn write_binary(&mut self, table: &Table) {
+        let name = table.name.as_bytes();
+        self.out.write_all(&[name.len() as u8]).unwrap();
+        self.out.write_all(name).unwrap();
+        let storage = match table.storage {
+            Storage::Const => 0,
+            Storage::Static => 1,
+        };
+        self.out.write_all(&[storage, table.element.to_code(), table.shape.len() as u8]).unwrap();
+        for len in &table.shape {
+            self.out.write_all(&(*len as u32).to_le_bytes()).unwrap();
+        }
+        let width = table.element.byte_width();
+        for value in &table.values {
+            self.out.write_all(&value.to_le_bytes()[..width]).unwrap();
+        }
+    }
+}
+
+// Writes the rows of a multi-dimensional array, with each innermost row on a single line
+fn write_nested(out: &mut dyn Write, shape: &[usize], elements: &[String], depth: usize, trailing_comma: bool) {
+    let indent = "    ".repeat(depth);
+    let rows: Vec<&[String]> = elements.chunks(elements.len() / shape[0]).collect();
+    for (i, row) in rows.iter().enumerate() {
+        let separator = if trailing_comma || i + 1 < rows.len() { "," } else { "" };
+        if shape.len() == 2 {
+            writeln!(out, "{}[{}]{}", indent, row.join(", "), separator).unwrap();
+        } else {
+            writeln!(out, "{}[", indent).unwrap();
+            write_nested(out, &shape[1..], row, depth + 1, trailing_comma);
+            writeln!(out, "{}]{}", indent, separator).unwrap();
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BinaryTableError {
+    InvalidHeader,
+    UnsupportedVersion(u16),
+    InvalidElement(u8),
+    UnexpectedEof,
+}
+
+impl fmt::Display for BinaryTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryTableError::InvalidHeader => write!(f, "not a diesel-gen table blob"),
+            BinaryTableError::UnsupportedVersion(version) => write!(f, "unsupported table blob version {}", version),
+            BinaryTableError::InvalidElement(code) => write!(f, "invalid element type {}", code),
+            BinaryTableError::UnexpectedEof => write!(f, "unexpected end of table blob"),
+        }
+    }
+}
+
+/// Loads the tables from a blob written in `OutputFormat::Binary`.
+pub fn read_binary_tables(bytes: &[u8]) -> Result<Vec<Table>, BinaryTableError> {
+    let mut reader = ByteReader { bytes, position: 0 };
+
+    if reader.take(BINARY_MAGIC.len())? != BINARY_MAGIC {
+        return Err(BinaryTableError::InvalidHeader);
+    }
+    let version = reader.read_le(2)? as u16;
+    if version != BINARY_VERSION {
+        return Err(BinaryTableError::UnsupportedVersion(version));
+    }
+
+    let mut tables = Vec::new();
+    while reader.position < bytes.len() {
+        let name_len = reader.read_le(1)? as usize;
+        let name = String::from_utf8_lossy(reader.take(name_len)?).to_string();
+        let storage = match reader.read_le(1)? {
+            0 => Storage::Const,
+            _ => Storage::Static,
+        };
+        let code = reader.read_le(1)? as u8;
+        let element = Element::from_code(code).ok_or(BinaryTableError::InvalidElement(code))?;
+        let dimensions = reader.read_le(1)? as usize;
+        let shape = (0..dimensions)
+            .map(|_| reader.read_le(4).map(|len| len as usize))
+            .collect::<Result<Vec<usize>, BinaryTableError>>()?;
+
+        let len = shape.iter().product::<usize>() * element.values_per_element();
+        let values = (0..len)
+            .map(|_| reader.read_le(element.byte_width()))
+            .collect::<Result<Vec<u64>, BinaryTableError>>()?;
+
+        tables.push(Table { name, storage, element, shape, values });
+    }
+    Ok(tables)
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BinaryTableError> {
+        let end = self.position + len;
+        if end > self.bytes.len() {
+            return Err(BinaryTableError::UnexpectedEof);
+        }
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_le(&mut self, width: usize) -> Result<u64, BinaryTableError> {
+        let mut buffer = [0u8; 8];
+        buffer[..width].copy_from_slice(self.take(width)?);
+        Ok(u64::from_le_bytes(buffer))
+    }
+}
@@ -1,17 +1,13 @@
-use std::fs::File;
+use diesel_core::color::NUM_COLORS;
+use diesel_core::square::NUM_SQUARES;
+use diesel_core::tables::{PAWN_ATTACKS, PAWN_MOVES};
 
-pub fn generate_pawn_moves() {
+use crate::output::{Storage, Table, TableWriter};
 
+pub fn write_pawn_moves(w: &mut TableWriter) {
+    w.write_table(&Table::bitboards("PAWN_MOVES", Storage::Const, &[NUM_COLORS, NUM_SQUARES], PAWN_MOVES.iter().flatten()));
 }
 
-pub fn generate_pawn_attacks() {
-
-}
-
-pub fn write_pawn_moves(f: &mut File) {
-
+pub fn write_pawn_attacks(w: &mut TableWriter) {
+    w.write_table(&Table::bitboards("PAWN_ATTACKS", Storage::Const, &[NUM_COLORS, NUM_SQUARES], PAWN_ATTACKS.iter().flatten()));
 }
-
-pub fn write_pawn_attacks(f: &mut File) {
-
-}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use diesel_core::direction::NUM_RAY_DIRECTIONS;
+use diesel_core::square::NUM_SQUARES;
+use diesel_core::tables::{BETWEEN, LINE, RAYS};
+
+use crate::output::{Storage, Table, TableWriter};
+
+pub fn write_rays(w: &mut TableWriter) {
+    w.write_table(&Table::bitboards("RAYS", Storage::Static, &[NUM_RAY_DIRECTIONS, NUM_SQUARES], RAYS.iter().flatten()));
+}
+
+pub fn write_between_and_line(w: &mut TableWriter) {
+    w.write_table(&Table::bitboards("BETWEEN", Storage::Static, &[NUM_SQUARES, NUM_SQUARES], BETWEEN.iter().flatten()));
+    w.write_table(&Table::bitboards("LINE", Storage::Static, &[NUM_SQUARES, NUM_SQUARES], LINE.iter().flatten()));
+}
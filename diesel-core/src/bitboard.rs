@@ -1,6 +1,7 @@
 use core::fmt;
-use std::fmt::Formatter;
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use core::fmt::Formatter;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr, Sub, SubAssign};
+use crate::direction::Direction;
 use crate::file::File;
 use crate::rank::Rank;
 use crate::square::Square;
@@ -9,6 +10,12 @@ use crate::square::Square;
 pub struct BitBoard(pub u64);
 
 pub const EMPTY_BITBOARD: BitBoard = BitBoard(0);
+pub const FULL_BITBOARD: BitBoard = BitBoard(!0);
+
+const NOT_A_FILE: u64 = !0x0101_0101_0101_0101;
+const NOT_H_FILE: u64 = !0x8080_8080_8080_8080;
+const NOT_AB_FILE: u64 = !0x0303_0303_0303_0303;
+const NOT_GH_FILE: u64 = !0xC0C0_C0C0_C0C0_C0C0;
 
 // Logical AND Implementation
 impl BitAnd for BitBoard {
@@ -67,12 +74,62 @@ impl BitXorAssign for BitBoard {
     }
 }
 
+// Set Difference Implementation
+impl Sub for BitBoard {
+    type Output = BitBoard;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        BitBoard(self.0 & !rhs.0)
+    }
+}
+
+// Set Difference Assignment Implementation
+impl SubAssign for BitBoard {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+// Raw Left Shift Implementation, bits shifted past h8 are lost and files are not masked
+impl Shl<u32> for BitBoard {
+    type Output = BitBoard;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        BitBoard(self.0.checked_shl(rhs).unwrap_or(0))
+    }
+}
+
+// Raw Right Shift Implementation, bits shifted past a1 are lost and files are not masked
+impl Shr<u32> for BitBoard {
+    type Output = BitBoard;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        BitBoard(self.0.checked_shr(rhs).unwrap_or(0))
+    }
+}
+
+// Iterates over the set squares from a1 to h8
+impl Iterator for BitBoard {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop_lsb()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for BitBoard {}
+
 impl BitBoard {
-    pub fn new(value: u64) -> Self {
+    pub const fn new(value: u64) -> Self {
         BitBoard(value)
     }
 
-    pub fn from_square(sq: Square) -> BitBoard {
+    pub const fn from_square(sq: Square) -> BitBoard {
         BitBoard(1u64 << sq.to_int())
     }
 
@@ -101,10 +158,123 @@ impl BitBoard {
     pub fn is_empty(self) -> bool {
         self == EMPTY_BITBOARD
     }
+
+    pub const fn contains(self, square: Square) -> bool {
+        self.0 & (1u64 << square.to_int()) != 0
+    }
+
+    /// Returns the least significant set square without removing it.
+    pub const fn lsb(self) -> Option<Square> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(Square::new(self.0.trailing_zeros() as u8))
+        }
+    }
+
+    /// Returns the most significant set square without removing it.
+    pub const fn msb(self) -> Option<Square> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(Square::new(63 - self.0.leading_zeros() as u8))
+        }
+    }
+
+    /// Removes and returns the least significant set square.
+    pub fn pop_lsb(&mut self) -> Option<Square> {
+        let square = self.lsb()?;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+
+    /// Shifts every square one step in `direction`, dropping squares that would wrap around a file edge.
+    pub const fn shift(self, direction: Direction) -> BitBoard {
+        let b = self.0;
+        BitBoard(match direction {
+            Direction::North => b << 8,
+            Direction::NorthEast => (b & NOT_H_FILE) << 9,
+            Direction::East => (b & NOT_H_FILE) << 1,
+            Direction::SouthEast => (b & NOT_H_FILE) >> 7,
+            Direction::South => b >> 8,
+            Direction::SouthWest => (b & NOT_A_FILE) >> 9,
+            Direction::West => (b & NOT_A_FILE) >> 1,
+            Direction::NorthWest => (b & NOT_A_FILE) << 7,
+            Direction::NorthNorthEast => (b & NOT_H_FILE) << 17,
+            Direction::EastNorthEast => (b & NOT_GH_FILE) << 10,
+            Direction::EastSouthEast => (b & NOT_GH_FILE) >> 6,
+            Direction::SouthSouthEast => (b & NOT_H_FILE) >> 15,
+            Direction::SouthSouthWest => (b & NOT_A_FILE) >> 17,
+            Direction::WestSouthWest => (b & NOT_AB_FILE) >> 10,
+            Direction::WestNorthWest => (b & NOT_AB_FILE) << 6,
+            Direction::NorthNorthWest => (b & NOT_A_FILE) << 15,
+        })
+    }
+
+    /// Returns the squares on or above each set square, on the same file.
+    pub const fn north_fill(self) -> BitBoard {
+        let mut b = self.0;
+        b |= b << 8;
+        b |= b << 16;
+        b |= b << 32;
+        BitBoard(b)
+    }
+
+    /// Returns the squares on or below each set square, on the same file.
+    pub const fn south_fill(self) -> BitBoard {
+        let mut b = self.0;
+        b |= b >> 8;
+        b |= b >> 16;
+        b |= b >> 32;
+        BitBoard(b)
+    }
+
+    /// Returns every square on a file containing a set square.
+    pub const fn file_fill(self) -> BitBoard {
+        BitBoard(self.north_fill().0 | self.south_fill().0)
+    }
+
+    /// Kogge-Stone occluded fill: the set squares plus every square reachable from them in `direction`
+    /// through `empty`, stopping before the first non-empty square.
+    pub const fn occluded_fill(self, direction: Direction, empty: BitBoard) -> BitBoard {
+        let mut generator = self;
+        let mut propagator = empty;
+        let mut step = 0;
+        while step < 3 {
+            // Each round doubles the distance covered: 1, 2, then 4 squares
+            let mut i = 0;
+            let mut shifted = generator;
+            let mut shifted_propagator = propagator;
+            while i < (1 << step) {
+                shifted = shifted.shift(direction);
+                shifted_propagator = shifted_propagator.shift(direction);
+                i += 1;
+            }
+            generator = BitBoard(generator.0 | (propagator.0 & shifted.0));
+            propagator = BitBoard(propagator.0 & shifted_propagator.0);
+            step += 1;
+        }
+        generator
+    }
+
+    /// Returns the squares attacked in `direction` by a slider on each set square, including the first blocker.
+    pub const fn sliding_attacks(self, direction: Direction, empty: BitBoard) -> BitBoard {
+        self.occluded_fill(direction, empty).shift(direction)
+    }
+
+    /// Mirrors the board across the horizontal axis, so a1 becomes a8.
+    pub const fn flip_vertical(self) -> BitBoard {
+        BitBoard(self.0.swap_bytes())
+    }
+
+    /// Mirrors the board across the vertical axis, so a1 becomes h1.
+    pub const fn mirror_horizontal(self) -> BitBoard {
+        BitBoard(self.0.reverse_bits().swap_bytes())
+    }
 }
 
 impl fmt::Display for BitBoard {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         writeln!(f, "  a b c d e f g h")?;
         writeln!(f, " -----------------")?;
 
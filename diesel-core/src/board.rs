@@ -1,59 +1,129 @@
 mod parse;
-mod builder;
-
-use std::fmt;
-use std::str::FromStr;
+pub mod builder;
+mod validate;
+mod encoding;
+mod movegen;
+mod make_move;
+mod see;
+pub mod render;
+#[cfg(feature = "alloc")]
+pub mod svg;
+
+pub use encoding::{BoardDecodeError, MAX_ENCODED_LEN};
+#[cfg(feature = "serde")]
+pub(crate) use encoding::FULL_ENCODED_LEN;
+pub use parse::BoardParseError;
+pub use validate::InvariantViolation;
+
+use core::fmt;
+use core::str::FromStr;
 
 use crate::bitboard::{BitBoard, EMPTY_BITBOARD};
 use crate::board::builder::BoardBuilder;
+use crate::board::render::BoardRenderer;
 use crate::castling::CastlingRights;
 use crate::color::{Color, NUM_COLORS};
-use crate::file::File;
-use crate::piece::{NUM_PIECES, Piece};
+use crate::piece::{ALL_PIECES, NUM_PIECES, Piece};
 use crate::rank::Rank;
-use crate::square::{ALL_SQUARES, Square};
+use crate::square::{ALL_SQUARES, NUM_SQUARES, Square};
+use crate::tables::{ADJACENT_FILE_MASKS, RANK_MASKS};
+use crate::zobrist::{get_castling_key, get_en_passant_key, get_piece_key, get_side_key};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Board {
     piece_bitboards: [[BitBoard; NUM_PIECES]; NUM_COLORS],
     side_bitboards: [BitBoard; NUM_COLORS],
     occupancy_bitboard: BitBoard,
+    // Redundant per-square lookup, kept in sync with the bitboards
+    mailbox: [Option<(Piece, Color)>; NUM_SQUARES],
 
     to_move: Color,
     en_passant_square: Option<Square>,
     castling_rights: CastlingRights,
+    halfmove_clock: u8,
+    fullmove_counter: u16,
     hash: u64,
 }
 
 impl Board {
     pub fn new() -> Self {
-        Self {
+        let mut board = Self {
             piece_bitboards: [[EMPTY_BITBOARD; NUM_PIECES]; NUM_COLORS],
             side_bitboards: [EMPTY_BITBOARD; NUM_COLORS],
             occupancy_bitboard: EMPTY_BITBOARD,
+            mailbox: [None; NUM_SQUARES],
             to_move: Color::White,
             en_passant_square: None,
             castling_rights: CastlingRights::empty(),
+            halfmove_clock: 0,
+            fullmove_counter: 1,
             hash: 0,
-        }
+        };
+        board.hash = board.compute_hash();
+        board
     }
 
+    /// Places `piece` on every square of `bitboard`, replacing whatever was there.
     pub fn set_piece_at(&mut self, bitboard: BitBoard, piece: Piece, color: Color) {
+        for square in bitboard {
+            self.remove_piece(square);
+            self.put_piece(square, piece, color);
+        }
+        self.hash = self.compute_hash();
+        self.debug_check_mailbox();
+    }
+
+    /// Clears every square of `bitboard`.
+    pub fn clear_piece_at(&mut self, bitboard: BitBoard) {
+        for square in bitboard {
+            self.remove_piece(square);
+        }
+        self.hash = self.compute_hash();
+        self.debug_check_mailbox();
+    }
+
+    pub fn get_piece_at(&self, square: Square) -> Option<(Piece, Color)> {
+        self.mailbox[square.to_index()]
+    }
+
+    // Low-level placement that keeps the bitboards and the mailbox in sync, but leaves the hash alone
+    fn put_piece(&mut self, square: Square, piece: Piece, color: Color) {
+        let bitboard = BitBoard::from_square(square);
         self.piece_bitboards[color.to_index()][piece.to_index()] |= bitboard;
         self.side_bitboards[color.to_index()] |= bitboard;
         self.occupancy_bitboard |= bitboard;
+        self.mailbox[square.to_index()] = Some((piece, color));
     }
 
-    pub fn get_piece_at(&self, square: Square) -> Option<(Piece, Color)> {
-        let bitboard_mask = BitBoard::new(1u64 << square.to_int());
-        for &color in &[Color::White, Color::Black] {
-            for &piece in &[Piece::Pawn, Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen, Piece::King] {
-                if self.piece_bitboards[color.to_index()][piece.to_index()] & bitboard_mask != EMPTY_BITBOARD {
-                    return Some((piece, color));
-                }
-            }
-        }
-        None
+    fn remove_piece(&mut self, square: Square) -> Option<(Piece, Color)> {
+        let (piece, color) = self.mailbox[square.to_index()]?;
+        let bitboard = BitBoard::from_square(square);
+        self.piece_bitboards[color.to_index()][piece.to_index()] -= bitboard;
+        self.side_bitboards[color.to_index()] -= bitboard;
+        self.occupancy_bitboard -= bitboard;
+        self.mailbox[square.to_index()] = None;
+        Some((piece, color))
+    }
+
+    /// Returns `true` if the mailbox agrees with the piece bitboards on every square.
+    pub fn is_mailbox_consistent(&self) -> bool {
+        ALL_SQUARES.iter().all(|&square| {
+            let from_bitboards = ALL_PIECES.iter().flat_map(|&piece| {
+                [Color::White, Color::Black]
+                    .into_iter()
+                    .filter(move |color| self.piece_bitboards[color.to_index()][piece.to_index()].contains(square))
+                    .map(move |color| (piece, color))
+            });
+            from_bitboards.eq(self.mailbox[square.to_index()])
+        })
+    }
+
+    fn debug_check_mailbox(&self) {
+        debug_assert!(self.is_mailbox_consistent(), "mailbox out of sync with bitboards");
+    }
+
+    pub fn get_piece_bitboard(&self, piece: Piece, color: Color) -> BitBoard {
+        self.piece_bitboards[color.to_index()][piece.to_index()]
     }
 
     pub fn get_pawn_bitboard(&self, color: Color) -> BitBoard {
@@ -148,6 +218,113 @@ impl Board {
         self.to_move
     }
 
+    pub fn get_en_passant_square(&self) -> Option<Square> {
+        self.en_passant_square
+    }
+
+    pub fn get_castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    pub fn get_halfmove_clock(&self) -> u8 {
+        self.halfmove_clock
+    }
+
+    pub fn get_fullmove_counter(&self) -> u16 {
+        self.fullmove_counter
+    }
+
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Computes the Zobrist hash from scratch using the Polyglot conventions: the side key is
+    /// included when White is to move, and the en passant file only when a pawn can capture there.
+    pub fn compute_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        for color in [Color::White, Color::Black] {
+            for piece in ALL_PIECES {
+                for square in self.piece_bitboards[color.to_index()][piece.to_index()] {
+                    hash ^= get_piece_key(piece, color, square);
+                }
+            }
+        }
+
+        hash ^= get_castling_key(self.castling_rights);
+        hash ^= self.get_en_passant_key();
+
+        if self.to_move == Color::White {
+            hash ^= get_side_key();
+        }
+
+        hash
+    }
+
+    // The en passant file only counts towards the hash when a pawn can capture there
+    fn get_en_passant_key(&self) -> u64 {
+        let Some(en_passant_square) = self.en_passant_square else {
+            return 0;
+        };
+        let capture_rank = match self.to_move {
+            Color::White => Rank::Fifth,
+            Color::Black => Rank::Fourth,
+        };
+        let capturers = self.get_pawn_bitboard(self.to_move)
+            & RANK_MASKS[capture_rank.to_index()]
+            & ADJACENT_FILE_MASKS[en_passant_square.get_file().to_index()];
+        if capturers.is_empty() {
+            0
+        } else {
+            get_en_passant_key(en_passant_square.get_file())
+        }
+    }
+
+    /// Mirrors the board vertically and swaps the colors of every piece, the side to move, the castling
+    /// rights and the en passant square. The result is the same position seen from the other side.
+    pub fn flip_colors(&self) -> Board {
+        let mut board = self.transform(|square| square.flip(), true);
+        board.castling_rights = self.castling_rights.flip();
+        board.hash = board.compute_hash();
+        board
+    }
+
+    /// Mirrors the board across the d/e file boundary. Returns `None` if either side can still castle,
+    /// as castling is not symmetric between the wings.
+    pub fn mirror_horizontal(&self) -> Option<Board> {
+        if self.castling_rights != CastlingRights::empty() {
+            return None;
+        }
+        let mut board = self.transform(|square| Square::new(square.to_int() ^ 7), false);
+        board.hash = board.compute_hash();
+        Some(board)
+    }
+
+    /// Rotates the board by 180 degrees and swaps the colors, which is `flip_colors` followed by
+    /// `mirror_horizontal`. Returns `None` if either side can still castle.
+    pub fn rotate(&self) -> Option<Board> {
+        self.flip_colors().mirror_horizontal()
+    }
+
+    // Moves every piece to `map_square(square)`, optionally swapping the colors of the pieces and the
+    // side to move. Castling rights and the hash are left for the caller to fix up.
+    fn transform(&self, map_square: impl Fn(Square) -> Square, swap_colors: bool) -> Board {
+        let mut board = Board::new();
+        for square in ALL_SQUARES {
+            if let Some((piece, color)) = self.get_piece_at(square) {
+                let color = if swap_colors { !color } else { color };
+                board.put_piece(map_square(square), piece, color);
+            }
+        }
+        board.to_move = if swap_colors { !self.to_move } else { self.to_move };
+        board.en_passant_square = self.en_passant_square.map(&map_square);
+        board.castling_rights = self.castling_rights;
+        board.halfmove_clock = self.halfmove_clock;
+        board.fullmove_counter = self.fullmove_counter;
+        board.debug_check_mailbox();
+        board
+    }
+
     fn set_to_move(&mut self, color: Color) {
         self.to_move = color;
     }
@@ -163,34 +340,7 @@ impl Board {
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "  a b c d e f g h")?;
-        writeln!(f, " -----------------")?;
-
-        for rank in (0..8).rev() {
-            write!(f, "{}|", rank + 1)?;
-
-            for file in 0..8 {
-                let square = Square::create_square(Rank::from_index(rank), File::from_index(file));
-                let piece = self.get_piece_at(square);
-
-                write!(f, " ")?;
-
-                match piece {
-                    Some((piece, color)) => {
-                        let piece_str = piece.to_fancy_string(color);
-                        write!(f, "{}", piece_str)?;
-                    }
-                    None => {
-                        write!(f, ".")?;
-                    }
-                }
-            }
-
-            writeln!(f, "|")?;
-        }
-
-        writeln!(f, " -----------------")?;
-        write!(f, "  a b c d e f g h")
+        BoardRenderer::new().write(self, f)
     }
 }
 
@@ -201,28 +351,36 @@ impl Default for Board {
     }
 }
 
+/// Fails when a castling right or the en passant square is not backed by the placed pieces.
 impl TryFrom<&BoardBuilder> for Board {
-    type Error = ();
+    type Error = InvariantViolation;
 
     fn try_from(board_builder: &BoardBuilder) -> Result<Self, Self::Error> {
         let mut board = Board::new();
 
+        // Placed without rehashing, as the hash is computed once every field is set
         for square in ALL_SQUARES.iter() {
             if let Some((piece, color)) = board_builder.get_board()[square.to_index()] {
-                board.set_piece_at(BitBoard::from_square(*square), piece, color);
+                board.put_piece(*square, piece, color);
             }
         }
 
         board.set_to_move(board_builder.get_side_to_move());
         board.set_castling_rights(board_builder.get_castling_rights());
         board.set_en_passant_square(board_builder.get_en_passant());
+        board.halfmove_clock = board_builder.get_halfmove_clock();
+        board.fullmove_counter = board_builder.get_fullmove_counter();
+        board.validate_castling_rights()?;
+        board.validate_en_passant_square()?;
+        board.hash = board.compute_hash();
+        board.debug_check_mailbox();
 
         Ok(board)
     }
 }
 
 impl TryFrom<&mut BoardBuilder> for Board {
-    type Error = ();
+    type Error = InvariantViolation;
 
     fn try_from(board_builder: &mut BoardBuilder) -> Result<Self, Self::Error> {
         (&*board_builder).try_into()
@@ -230,7 +388,7 @@ impl TryFrom<&mut BoardBuilder> for Board {
 }
 
 impl TryFrom<BoardBuilder> for Board {
-    type Error = ();
+    type Error = InvariantViolation;
 
     fn try_from(board_builder: BoardBuilder) -> Result<Self, Self::Error> {
         (&board_builder).try_into()
@@ -80,6 +80,8 @@ impl BoardBuilder {
         self
     }
 
+    /// Panics when a castling right or the en passant square is not backed by the placed pieces.
+    /// Use `Board::try_from` to handle that instead.
     pub fn build(&mut self) -> Board {
         Board::try_from(self).unwrap()
     }
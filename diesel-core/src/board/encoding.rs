@@ -0,0 +1,130 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::bitboard::BitBoard;
+use crate::board::{Board, InvariantViolation};
+use crate::castling::CastlingRights;
+use crate::color::Color;
+use crate::file::NUM_FILES;
+use crate::piece::{ALL_PIECES, NUM_PIECES};
+use crate::rank::Rank;
+#[cfg(feature = "serde")]
+use crate::square::NUM_SQUARES;
+use crate::square::Square;
+
+// Layout, all multi-byte values little endian:
+//
+//   0..8    occupancy bitboard
+//   8       bits 0-3 castling rights, bit 4 set when Black is to move
+//   9       en passant file + 1, or 0 for none (the rank follows from the side to move)
+//   10      halfmove clock
+//   11..13  fullmove counter
+//   13..    one 4-bit piece code per occupied square from a1 to h8, low nibble first
+//
+// A piece code is the piece index for White and the piece index + 6 for Black.
+const HEADER_LEN: usize = 13;
+const BLACK_TO_MOVE: u8 = 0b0001_0000;
+const CASTLING_MASK: u8 = 0b0000_1111;
+
+/// The encoded size of a legal position, which has at most 32 pieces.
+pub const MAX_ENCODED_LEN: usize = HEADER_LEN + 32 / 2;
+
+/// The encoded size of a board with every square occupied, the most `Board::to_bytes` ever writes.
+#[cfg(feature = "serde")]
+pub(crate) const FULL_ENCODED_LEN: usize = HEADER_LEN + NUM_SQUARES / 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardDecodeError {
+    /// The input ended before every occupied square had a piece code
+    UnexpectedEnd,
+    /// The input continues past the last piece code
+    TrailingBytes,
+    InvalidPieceCode(u8),
+    InvalidFlags(u8),
+    InvalidEnPassantFile(u8),
+    /// The decoded position breaks an invariant, such as a castling right without its king and rook
+    InvalidPosition(InvariantViolation),
+}
+
+impl Board {
+    /// Packs the position into `MAX_ENCODED_LEN` bytes or fewer (positions with more than 32 pieces
+    /// take one extra byte per two pieces). Far smaller than FEN and much cheaper to decode.
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let occupancy = self.get_occupancy_bitboard();
+        let mut bytes = Vec::with_capacity(HEADER_LEN + (occupancy.count_bits() as usize).div_ceil(2));
+
+        bytes.extend_from_slice(&occupancy.0.to_le_bytes());
+        let side = if self.to_move == Color::Black { BLACK_TO_MOVE } else { 0 };
+        bytes.push(self.castling_rights.to_bytes() & CASTLING_MASK | side);
+        bytes.push(self.en_passant_square.map_or(0, |square| square.get_file().to_index() as u8 + 1));
+        bytes.push(self.halfmove_clock);
+        bytes.extend_from_slice(&self.fullmove_counter.to_le_bytes());
+
+        for (i, square) in occupancy.enumerate() {
+            // Every occupied square has an entry in the mailbox
+            let (piece, color) = self.get_piece_at(square).unwrap();
+            let code = (piece.to_index() + color.to_index() * NUM_PIECES) as u8;
+            if i % 2 == 0 {
+                bytes.push(code);
+            } else {
+                *bytes.last_mut().unwrap() |= code << 4;
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a position written by [`Board::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Board, BoardDecodeError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(BoardDecodeError::UnexpectedEnd);
+        }
+        let occupancy = BitBoard(u64::from_le_bytes(bytes[0..8].try_into().unwrap()));
+        let flags = bytes[8];
+        let en_passant_file = bytes[9];
+        let piece_count = occupancy.count_bits() as usize;
+
+        let expected_len = HEADER_LEN + piece_count.div_ceil(2);
+        if bytes.len() < expected_len {
+            return Err(BoardDecodeError::UnexpectedEnd);
+        }
+        if bytes.len() > expected_len {
+            return Err(BoardDecodeError::TrailingBytes);
+        }
+        if flags & !(CASTLING_MASK | BLACK_TO_MOVE) != 0 {
+            return Err(BoardDecodeError::InvalidFlags(flags));
+        }
+        if en_passant_file as usize > NUM_FILES {
+            return Err(BoardDecodeError::InvalidEnPassantFile(en_passant_file));
+        }
+
+        let mut board = Board::new();
+        for (i, square) in occupancy.enumerate() {
+            let code = (bytes[HEADER_LEN + i / 2] >> (4 * (i % 2))) & 0x0F;
+            if code as usize >= 2 * NUM_PIECES {
+                return Err(BoardDecodeError::InvalidPieceCode(code));
+            }
+            let color = if (code as usize) < NUM_PIECES { Color::White } else { Color::Black };
+            board.put_piece(square, ALL_PIECES[code as usize % NUM_PIECES], color);
+        }
+
+        board.to_move = if flags & BLACK_TO_MOVE != 0 { Color::Black } else { Color::White };
+        board.castling_rights = CastlingRights::from_bytes(flags & CASTLING_MASK);
+        board.en_passant_square = match en_passant_file {
+            0 => None,
+            file => {
+                let rank = match board.to_move {
+                    Color::White => Rank::Sixth,
+                    Color::Black => Rank::Third,
+                };
+                Some(Square::new(rank as u8 * 8 + file - 1))
+            }
+        };
+        board.halfmove_clock = bytes[10];
+        board.fullmove_counter = u16::from_le_bytes([bytes[11], bytes[12]]);
+        board.hash = board.compute_hash();
+        board.validate_invariants().map_err(BoardDecodeError::InvalidPosition)?;
+
+        Ok(board)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::board::Board;
+use crate::board::movegen::{CASTLES, pawn_push};
+use crate::castling::{BLACK_CASTLING, BLACK_KING_SIDE, BLACK_QUEEN_SIDE, NO_CASTLING, WHITE_CASTLING, WHITE_KING_SIDE, WHITE_QUEEN_SIDE};
+use crate::chess_move::ChessMove;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::square::*;
+use crate::zobrist::{get_castling_key, get_piece_key, get_side_key};
+
+// The castling rights lost when a piece moves from or to a square
+const fn castling_rights_lost(square: Square) -> u8 {
+    match square {
+        A1 => WHITE_QUEEN_SIDE,
+        E1 => WHITE_CASTLING,
+        H1 => WHITE_KING_SIDE,
+        A8 => BLACK_QUEEN_SIDE,
+        E8 => BLACK_CASTLING,
+        H8 => BLACK_KING_SIDE,
+        _ => NO_CASTLING,
+    }
+}
+
+impl Board {
+    /// Returns the position after `chess_move`, which must be legal in this position.
+    pub fn make_move(&self, chess_move: ChessMove) -> Board {
+        let mut board = *self;
+        board.apply_move(chess_move);
+        board
+    }
+
+    /// Returns the position with the side to move passing its turn, which is only legal in search and only
+    /// when not in check.
+    pub fn make_null_move(&self) -> Board {
+        debug_assert!(!self.is_in_check(), "null move while in check");
+        let mut board = *self;
+        board.hash ^= board.get_en_passant_key();
+        board.en_passant_square = None;
+        board.halfmove_clock = board.halfmove_clock.saturating_add(1);
+        if board.to_move == Color::Black {
+            board.fullmove_counter += 1;
+        }
+        board.to_move = !board.to_move;
+        board.hash ^= get_side_key();
+        debug_assert_eq!(board.validate_invariants(), Ok(()), "invalid position after a null move");
+        board
+    }
+
+    fn apply_move(&mut self, chess_move: ChessMove) {
+        let us = self.to_move;
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+
+        // The en passant and castling keys depend on the whole position, so they are removed
+        // up front and added back once the move is made
+        self.hash ^= self.get_en_passant_key();
+        self.hash ^= get_castling_key(self.castling_rights);
+
+        let (piece, _) = self.remove_piece(from).expect("no piece to move");
+        self.hash ^= get_piece_key(piece, us, from);
+
+        let mut captured = self.remove_piece(to);
+        if let Some((captured_piece, captured_color)) = captured {
+            self.hash ^= get_piece_key(captured_piece, captured_color, to);
+        }
+        if piece == Piece::Pawn && Some(to) == self.en_passant_square {
+            let captured_square = pawn_push(to, !us).expect("the captured pawn is on the board");
+            captured = self.remove_piece(captured_square);
+            self.hash ^= get_piece_key(Piece::Pawn, !us, captured_square);
+        }
+
+        let placed = chess_move.get_promotion().unwrap_or(piece);
+        self.put_piece(to, placed, us);
+        self.hash ^= get_piece_key(placed, us, to);
+
+        if piece == Piece::King {
+            if let Some(castle) = CASTLES[us.to_index()].iter().find(|castle| castle.king_from == from && castle.king_to == to) {
+                self.remove_piece(castle.rook_from);
+                self.put_piece(castle.rook_to, Piece::Rook, us);
+                self.hash ^= get_piece_key(Piece::Rook, us, castle.rook_from) ^ get_piece_key(Piece::Rook, us, castle.rook_to);
+            }
+        }
+
+        self.castling_rights.remove(castling_rights_lost(from) | castling_rights_lost(to));
+        self.en_passant_square = match piece {
+            Piece::Pawn if from.to_int().abs_diff(to.to_int()) == 16 => pawn_push(from, us),
+            _ => None,
+        };
+        self.halfmove_clock = if piece == Piece::Pawn || captured.is_some() {
+            0
+        } else {
+            self.halfmove_clock.saturating_add(1)
+        };
+        if us == Color::Black {
+            self.fullmove_counter += 1;
+        }
+        self.to_move = !us;
+
+        self.hash ^= get_side_key();
+        self.hash ^= get_castling_key(self.castling_rights);
+        self.hash ^= self.get_en_passant_key();
+
+        debug_assert_eq!(self.validate_invariants(), Ok(()), "invalid position after {}", chess_move);
+    }
+}
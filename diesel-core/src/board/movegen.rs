@@ -0,0 +1,347 @@
+use crate::bitboard::{BitBoard, EMPTY_BITBOARD, FULL_BITBOARD};
+use crate::board::Board;
+use crate::castling::{BLACK_KING_SIDE, BLACK_QUEEN_SIDE, WHITE_KING_SIDE, WHITE_QUEEN_SIDE};
+use crate::chess_move::ChessMove;
+use crate::color::Color;
+use crate::move_list::MoveList;
+use crate::piece::Piece;
+use crate::rank::Rank;
+use crate::square::*;
+use crate::tables::{RANK_MASKS, get_between, get_bishop_moves, get_bishop_rays, get_king_moves, get_knight_moves, get_line, get_pawn_attacks, get_rook_moves, get_rook_rays};
+
+const PROMOTION_PIECES: [Piece; 4] = [Piece::Queen, Piece::Knight, Piece::Rook, Piece::Bishop];
+
+pub(super) struct Castle {
+    pub(super) right: u8,
+    pub(super) king_from: Square,
+    pub(super) king_to: Square,
+    pub(super) rook_from: Square,
+    pub(super) rook_to: Square,
+    // Squares that must be empty, and squares the king passes through that must not be attacked
+    empty: BitBoard,
+    safe: BitBoard,
+}
+
+const fn squares<const N: usize>(squares: [Square; N]) -> BitBoard {
+    let mut bitboard = EMPTY_BITBOARD;
+    let mut i = 0;
+    while i < N {
+        bitboard.0 |= BitBoard::from_square(squares[i]).0;
+        i += 1;
+    }
+    bitboard
+}
+
+pub(super) const CASTLES: [[Castle; 2]; 2] = [
+    [
+        Castle {
+            right: WHITE_KING_SIDE,
+            king_from: E1,
+            king_to: G1,
+            rook_from: H1,
+            rook_to: F1,
+            empty: squares([F1, G1]),
+            safe: squares([F1, G1]),
+        },
+        Castle {
+            right: WHITE_QUEEN_SIDE,
+            king_from: E1,
+            king_to: C1,
+            rook_from: A1,
+            rook_to: D1,
+            empty: squares([B1, C1, D1]),
+            safe: squares([C1, D1]),
+        },
+    ],
+    [
+        Castle {
+            right: BLACK_KING_SIDE,
+            king_from: E8,
+            king_to: G8,
+            rook_from: H8,
+            rook_to: F8,
+            empty: squares([F8, G8]),
+            safe: squares([F8, G8]),
+        },
+        Castle {
+            right: BLACK_QUEEN_SIDE,
+            king_from: E8,
+            king_to: C8,
+            rook_from: A8,
+            rook_to: D8,
+            empty: squares([B8, C8, D8]),
+            safe: squares([C8, D8]),
+        },
+    ],
+];
+
+impl Board {
+    pub fn get_king_square(&self, color: Color) -> Square {
+        self.get_king_bitboard(color).lsb().expect("every side has a king")
+    }
+
+    /// Returns the pieces of either color that attack `square`, treating `occupancy` as the blockers.
+    pub fn get_attackers_to(&self, square: Square, occupancy: BitBoard) -> BitBoard {
+        let bishops = self.get_bishop_bitboard(Color::White) | self.get_bishop_bitboard(Color::Black);
+        let rooks = self.get_rook_bitboard(Color::White) | self.get_rook_bitboard(Color::Black);
+        let queens = self.get_queen_bitboard(Color::White) | self.get_queen_bitboard(Color::Black);
+        let knights = self.get_knight_bitboard(Color::White) | self.get_knight_bitboard(Color::Black);
+        let kings = self.get_king_bitboard(Color::White) | self.get_king_bitboard(Color::Black);
+
+        (get_pawn_attacks(Color::White, square) & self.get_pawn_bitboard(Color::Black))
+            | (get_pawn_attacks(Color::Black, square) & self.get_pawn_bitboard(Color::White))
+            | (get_knight_moves(square) & knights)
+            | (get_king_moves(square) & kings)
+            | (get_bishop_moves(square, occupancy) & (bishops | queens))
+            | (get_rook_moves(square, occupancy) & (rooks | queens))
+    }
+
+    pub fn is_square_attacked(&self, square: Square, by: Color) -> bool {
+        self.is_attacked_with(square, by, self.get_occupancy_bitboard())
+    }
+
+    fn is_attacked_with(&self, square: Square, by: Color, occupancy: BitBoard) -> bool {
+        !(self.get_attackers_to(square, occupancy) & self.get_pieces_bitboard(by)).is_empty()
+    }
+
+    /// Returns the opposing pieces giving check to the side to move.
+    pub fn get_checkers(&self) -> BitBoard {
+        let king = self.get_king_square(self.to_move);
+        self.get_attackers_to(king, self.get_occupancy_bitboard()) & self.get_pieces_bitboard(!self.to_move)
+    }
+
+    pub fn is_in_check(&self) -> bool {
+        !self.get_checkers().is_empty()
+    }
+
+    /// Returns the side to move's pieces that are pinned to their king.
+    pub fn get_pinned(&self) -> BitBoard {
+        let us = self.to_move;
+        let them = !us;
+        let king = self.get_king_square(us);
+        let queens = self.get_queen_bitboard(them);
+        let snipers = (get_rook_rays(king) & (self.get_rook_bitboard(them) | queens))
+            | (get_bishop_rays(king) & (self.get_bishop_bitboard(them) | queens));
+
+        let mut pinned = EMPTY_BITBOARD;
+        for sniper in snipers {
+            let blockers = get_between(king, sniper) & self.get_occupancy_bitboard();
+            if blockers.count_bits() == 1 {
+                pinned |= blockers & self.get_pieces_bitboard(us);
+            }
+        }
+        pinned
+    }
+
+    /// Generates every legal move for the side to move.
+    pub fn get_legal_moves(&self) -> MoveList {
+        self.generate_moves(true, true)
+    }
+
+    /// Generates the legal captures, including en passant, and promotions.
+    pub fn get_noisy_moves(&self) -> MoveList {
+        self.generate_moves(true, false)
+    }
+
+    /// Generates the legal moves that neither capture nor promote, including castling.
+    pub fn get_quiet_moves(&self) -> MoveList {
+        self.generate_moves(false, true)
+    }
+
+    // Noisy moves capture or promote, quiet moves are everything else including castling
+    fn generate_moves(&self, noisy: bool, quiet: bool) -> MoveList {
+        let mut moves = MoveList::new();
+        let us = self.to_move;
+        let own = self.get_pieces_bitboard(us);
+        let enemy = self.get_pieces_bitboard(!us);
+        let occupancy = self.get_occupancy_bitboard();
+        let king = self.get_king_square(us);
+        let checkers = self.get_checkers();
+        let kind_mask = match (noisy, quiet) {
+            (true, true) => !own,
+            (true, false) => enemy,
+            (false, true) => !occupancy,
+            (false, false) => EMPTY_BITBOARD,
+        };
+
+        // The king may not step onto an attacked square, including those behind it on a slider's line
+        let without_king = occupancy - BitBoard::from_square(king);
+        for to in get_king_moves(king) & kind_mask {
+            if !self.is_attacked_with(to, !us, without_king) {
+                moves.push(ChessMove::new(king, to, None));
+            }
+        }
+
+        if checkers.count_bits() > 1 {
+            return moves;
+        }
+        // With a single checker, other pieces must capture it or block its line
+        let check_mask = match checkers.lsb() {
+            Some(checker) => get_between(king, checker) | checkers,
+            None => FULL_BITBOARD,
+        };
+        let pinned = self.get_pinned();
+        let pin_mask = |from: Square| if pinned.contains(from) { get_line(king, from) } else { FULL_BITBOARD };
+        let targets = kind_mask & check_mask;
+
+        for from in self.get_knight_bitboard(us) - pinned {
+            push_moves(&mut moves, from, get_knight_moves(from) & targets);
+        }
+        let queens = self.get_queen_bitboard(us);
+        for from in self.get_bishop_bitboard(us) | queens {
+            push_moves(&mut moves, from, get_bishop_moves(from, occupancy) & targets & pin_mask(from));
+        }
+        for from in self.get_rook_bitboard(us) | queens {
+            push_moves(&mut moves, from, get_rook_moves(from, occupancy) & targets & pin_mask(from));
+        }
+
+        // Pushes to the last rank promote, so they are noisy
+        let promotion_rank = RANK_MASKS[Rank::Eighth.to_index() * (1 - us.to_index())];
+        let push_mask = match (noisy, quiet) {
+            (true, true) => FULL_BITBOARD,
+            (true, false) => promotion_rank,
+            (false, true) => !promotion_rank,
+            (false, false) => EMPTY_BITBOARD,
+        };
+        for from in self.get_pawn_bitboard(us) {
+            let mut pawn_targets = self.get_pawn_pushes(from) & push_mask;
+            if noisy {
+                pawn_targets |= get_pawn_attacks(us, from) & enemy;
+            }
+            for to in pawn_targets & check_mask & pin_mask(from) {
+                if to.relative_rank(us) == Rank::Eighth {
+                    for piece in PROMOTION_PIECES {
+                        moves.push(ChessMove::new(from, to, Some(piece)));
+                    }
+                } else {
+                    moves.push(ChessMove::new(from, to, None));
+                }
+            }
+
+            if let Some(en_passant) = self.en_passant_square.filter(|_| noisy) {
+                if get_pawn_attacks(us, from).contains(en_passant) && self.is_legal_en_passant(from, en_passant) {
+                    moves.push(ChessMove::new(from, en_passant, None));
+                }
+            }
+        }
+
+        if quiet && checkers.is_empty() {
+            for castle in &CASTLES[us.to_index()] {
+                if self.castling_rights.has(castle.right)
+                    && (castle.empty & occupancy).is_empty()
+                    && castle.safe.into_iter().all(|square| !self.is_square_attacked(square, !us))
+                {
+                    moves.push(ChessMove::new(castle.king_from, castle.king_to, None));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Returns `true` if `chess_move` is legal in this position. Checks the one move against the
+    /// check and pin masks rather than generating every move.
+    pub fn is_legal(&self, chess_move: ChessMove) -> bool {
+        let us = self.to_move;
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+        let promotion = chess_move.get_promotion();
+        let Some((piece, color)) = self.get_piece_at(from) else {
+            return false;
+        };
+        if color != us || self.get_pieces_bitboard(us).contains(to) {
+            return false;
+        }
+
+        let occupancy = self.get_occupancy_bitboard();
+        let king = self.get_king_square(us);
+        let checkers = self.get_checkers();
+        if piece == Piece::King {
+            if promotion.is_some() {
+                return false;
+            }
+            if let Some(castle) = CASTLES[us.to_index()].iter().find(|castle| castle.king_from == from && castle.king_to == to) {
+                return checkers.is_empty()
+                    && self.castling_rights.has(castle.right)
+                    && (castle.empty & occupancy).is_empty()
+                    && castle.safe.into_iter().all(|square| !self.is_square_attacked(square, !us));
+            }
+            return get_king_moves(king).contains(to) && !self.is_attacked_with(to, !us, occupancy - BitBoard::from_square(king));
+        }
+
+        if checkers.count_bits() > 1 {
+            return false;
+        }
+        let check_mask = match checkers.lsb() {
+            Some(checker) => get_between(king, checker) | checkers,
+            None => FULL_BITBOARD,
+        };
+        let pin_mask = if self.get_pinned().contains(from) { get_line(king, from) } else { FULL_BITBOARD };
+
+        let targets = match piece {
+            Piece::Pawn => {
+                if Some(to) == self.en_passant_square && get_pawn_attacks(us, from).contains(to) {
+                    return promotion.is_none() && self.is_legal_en_passant(from, to);
+                }
+                let valid_promotion = match promotion {
+                    Some(piece) => to.relative_rank(us) == Rank::Eighth && PROMOTION_PIECES.contains(&piece),
+                    None => to.relative_rank(us) != Rank::Eighth,
+                };
+                if !valid_promotion {
+                    return false;
+                }
+                self.get_pawn_pushes(from) | (get_pawn_attacks(us, from) & self.get_pieces_bitboard(!us))
+            }
+            _ if promotion.is_some() => return false,
+            Piece::Knight => get_knight_moves(from),
+            Piece::Bishop => get_bishop_moves(from, occupancy),
+            Piece::Rook => get_rook_moves(from, occupancy),
+            Piece::Queen => get_bishop_moves(from, occupancy) | get_rook_moves(from, occupancy),
+            Piece::King => unreachable!("king moves are checked above"),
+        };
+        (targets & check_mask & pin_mask).contains(to)
+    }
+
+    // The empty squares a pawn can push to, one or two squares from its starting rank
+    fn get_pawn_pushes(&self, from: Square) -> BitBoard {
+        let us = self.to_move;
+        let occupancy = self.get_occupancy_bitboard();
+        let mut pushes = EMPTY_BITBOARD;
+        if let Some(single) = pawn_push(from, us).filter(|to| !occupancy.contains(*to)) {
+            pushes |= BitBoard::from_square(single);
+            if from.relative_rank(us) == Rank::Second {
+                if let Some(double) = pawn_push(single, us).filter(|to| !occupancy.contains(*to)) {
+                    pushes |= BitBoard::from_square(double);
+                }
+            }
+        }
+        pushes
+    }
+
+    // En passant removes two pieces from a rank at once, which the pin detection can't see, so the
+    // capture is played out on the occupancy and the king checked directly
+    fn is_legal_en_passant(&self, from: Square, en_passant: Square) -> bool {
+        let us = self.to_move;
+        let them = !us;
+        let captured = pawn_push(en_passant, them).expect("the captured pawn is on the board");
+        let occupancy = (self.get_occupancy_bitboard() - BitBoard::from_square(from) - BitBoard::from_square(captured))
+            | BitBoard::from_square(en_passant);
+        let enemy = self.get_pieces_bitboard(them) - BitBoard::from_square(captured);
+        let attackers = self.get_attackers_to(self.get_king_square(us), occupancy) & enemy;
+        attackers.is_empty()
+    }
+}
+
+fn push_moves(moves: &mut MoveList, from: Square, targets: BitBoard) {
+    for to in targets {
+        moves.push(ChessMove::new(from, to, None));
+    }
+}
+
+/// Returns the square in front of `square` from `color`'s side.
+pub(super) fn pawn_push(square: Square, color: Color) -> Option<Square> {
+    match color {
+        Color::White => square.up(),
+        Color::Black => square.down(),
+    }
+}
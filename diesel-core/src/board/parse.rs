@@ -1,11 +1,16 @@
-use std::str::FromStr;
-use crate::board::Board;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::board::{Board, InvariantViolation};
 use crate::board::builder::BoardBuilder;
-use crate::castling::{CastlingRights, WHITE_KING_SIDE, WHITE_QUEEN_SIDE, BLACK_KING_SIDE, BLACK_QUEEN_SIDE};
+use crate::castling::CastlingRights;
 use crate::color::Color;
-use crate::file::File;
+use crate::file::{File, NUM_FILES};
 use crate::piece::Piece;
-use crate::rank::Rank;
+use crate::rank::{NUM_RANKS, Rank};
 use crate::square::Square;
 
 #[derive(Debug)]
@@ -21,44 +26,95 @@ pub enum BoardParseError {
 
 impl Board {
     pub fn parse_fen(fen: &str) -> Result<Self, BoardParseError> {
-        let fields: Vec<&str> = fen.split(' ').collect();
-        if fields.len() != 6 {
+        let mut fields = [""; 6];
+        let mut split = fen.split(' ');
+        for field in fields.iter_mut() {
+            *field = split.next().ok_or(BoardParseError::InvalidFenString)?;
+        }
+        if split.next().is_some() {
             return Err(BoardParseError::InvalidFenString);
         }
 
         // Parse board
         let board = Self::parse_board(fields[0])
-            .map_err(|_| BoardParseError::InvalidBoard).unwrap();
+            .map_err(|_| BoardParseError::InvalidBoard)?;
 
         // Parse side to move
         let side_to_move = Self::parse_side_to_move(fields[1])
-            .map_err(|_| BoardParseError::InvalidSideToMove).unwrap();
+            .map_err(|_| BoardParseError::InvalidSideToMove)?;
 
         // Parse castling rights
         let castling_rights = Self::parse_castling_rights(fields[2])
-            .map_err(|_| BoardParseError::InvalidCastlingRights).unwrap();
+            .map_err(|_| BoardParseError::InvalidCastlingRights)?;
 
         // Parse en passant target square
         let en_passant_target_square = Self::parse_en_passant_target_square(fields[3])
-            .map_err(|_| BoardParseError::InvalidEnPassantTargetSquare).unwrap();
+            .map_err(|_| BoardParseError::InvalidEnPassantTargetSquare)?;
 
         // Parse halfmove clock
         let halfmove_clock = Self::parse_halfmove_clock(fields[4])
-            .map_err(|_| BoardParseError::InvalidHalfmoveClock).unwrap();
+            .map_err(|_| BoardParseError::InvalidHalfmoveClock)?;
 
         // Parse fullmove counter
         let fullmove_counter = Self::parse_fullmove_counter(fields[5])
-            .map_err(|_| BoardParseError::InvalidFullmoveCounter).unwrap();
+            .map_err(|_| BoardParseError::InvalidFullmoveCounter)?;
 
-        let mut board_builder: BoardBuilder = BoardBuilder::new().
+        let board_builder: BoardBuilder = BoardBuilder::new().
             set_board(board)
             .set_side_to_move(side_to_move)
             .set_castling_rights(castling_rights)
             .en_passant(en_passant_target_square)
             .halfmove_clock(halfmove_clock)
             .fullmove_counter(fullmove_counter);
-        let board: Board = board_builder.build();
-        Ok(board)
+        Board::try_from(board_builder).map_err(|violation| match violation {
+            InvariantViolation::CastlingRightsMismatch(_) => BoardParseError::InvalidCastlingRights,
+            InvariantViolation::InvalidEnPassantSquare(_) => BoardParseError::InvalidEnPassantTargetSquare,
+            _ => BoardParseError::InvalidBoard,
+        })
+    }
+
+    /// Writes the position as a FEN string.
+    #[cfg(feature = "alloc")]
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+        self.write_fen(&mut fen).expect("writing to a String cannot fail");
+        fen
+    }
+
+    /// Writes the position as FEN to `writer`, without allocating.
+    pub fn write_fen<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        for rank in (0..NUM_RANKS).rev() {
+            let mut empty = 0;
+            for file in 0..NUM_FILES {
+                match self.get_piece_at(Square::create_square(Rank::from_index(rank), File::from_index(file))) {
+                    Some((piece, color)) => {
+                        if empty > 0 {
+                            write!(writer, "{}", empty)?;
+                            empty = 0;
+                        }
+                        writer.write_char(piece.to_char(color))?;
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                write!(writer, "{}", empty)?;
+            }
+            if rank > 0 {
+                writer.write_char('/')?;
+            }
+        }
+
+        let side_to_move = match self.get_to_move() {
+            Color::White => 'w',
+            Color::Black => 'b',
+        };
+        write!(writer, " {} {} ", side_to_move, self.get_castling_rights())?;
+        match self.get_en_passant_square() {
+            Some(square) => write!(writer, "{}", square)?,
+            None => writer.write_char('-')?,
+        }
+        write!(writer, " {} {}", self.get_halfmove_clock(), self.get_fullmove_counter())
     }
 
     pub fn parse_shredder_fen(fen: &str) -> Result<Self, BoardParseError> {
@@ -68,9 +124,15 @@ impl Board {
     fn parse_board(str: &str) -> Result<[Option<(Piece, Color)>; 64], ()> {
         let mut board: [Option<(Piece, Color)>; 64] = [None; 64];
         for (rank, fileStr) in str.rsplit('/').enumerate() {
+            if rank >= NUM_RANKS {
+                return Err(());
+            }
             let rank = Rank::from_index(rank);
             let mut file = 0;
             for c in fileStr.chars() {
+                if file >= NUM_FILES {
+                    return Err(());
+                }
                 if let Some(offset) = c.to_digit(10) {
                     file += offset as usize;
                 } else {
@@ -94,25 +156,12 @@ impl Board {
     }
 
     fn parse_castling_rights(str: &str) -> Result<CastlingRights, ()> {
-        let mut castling_rights = CastlingRights::empty();
-        if str.contains('K') {
-            castling_rights |= CastlingRights::from_bytes(WHITE_KING_SIDE);
-        }
-        if str.contains('Q') {
-            castling_rights |= CastlingRights::from_bytes(WHITE_QUEEN_SIDE);
-        }
-        if str.contains('k') {
-            castling_rights |= CastlingRights::from_bytes(BLACK_KING_SIDE);
-        }
-        if str.contains('q') {
-            castling_rights |= CastlingRights::from_bytes(BLACK_QUEEN_SIDE);
-        }
-        Ok(castling_rights)
+        str.parse::<CastlingRights>()
     }
 
     fn parse_en_passant_target_square(str: &str) -> Result<Option<Square>, ()> {
         if str != "-" {
-            let square = str.parse::<Square>().map_err(|_| ()).unwrap();
+            let square = str.parse::<Square>().map_err(|_| ())?;
             // if square.rank() != 3 && square.rank() != 6 {
             //     return Err(());
             // }
@@ -122,7 +171,7 @@ impl Board {
     }
 
     fn parse_halfmove_clock(str: &str) -> Result<u8, ()> {
-        let halfmove_clock = str.parse::<u8>().map_err(|_| ()).unwrap();
+        let halfmove_clock = str.parse::<u8>().map_err(|_| ())?;
         if halfmove_clock > 100 {
             return Err(());
         }
@@ -130,7 +179,7 @@ impl Board {
     }
 
     fn parse_fullmove_counter(str: &str) -> Result<u16, ()> {
-        let fullmove_counter = str.parse::<u16>().map_err(|_| ()).unwrap();
+        let fullmove_counter = str.parse::<u16>().map_err(|_| ())?;
         if fullmove_counter == 0 {
             return Err(());
         }
@@ -0,0 +1,201 @@
+use core::fmt;
+
+use crate::bitboard::{BitBoard, EMPTY_BITBOARD};
+use crate::board::Board;
+use crate::color::Color;
+use crate::file::{File, NUM_FILES};
+use crate::rank::{NUM_RANKS, Rank};
+use crate::square::Square;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_LIGHT_SQUARE: &str = "\x1b[48;5;180m";
+const ANSI_DARK_SQUARE: &str = "\x1b[48;5;137m";
+const ANSI_HIGHLIGHT: &str = "\x1b[48;5;142m";
+const ANSI_WHITE_PIECE: &str = "\x1b[1;97m";
+const ANSI_BLACK_PIECE: &str = "\x1b[1;30m";
+
+// Marks a highlighted square when ANSI colors are off
+const HIGHLIGHT_MARKER: char = '*';
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum PieceStyle {
+    /// FEN letters, uppercase for White. Safe for any terminal.
+    Letters,
+    /// Unicode chess figurines.
+    Figurines,
+}
+
+/// Renders a board as text. The default settings match `impl Display for Board`.
+#[derive(Clone, Debug)]
+pub struct BoardRenderer {
+    piece_style: PieceStyle,
+    ansi_colors: bool,
+    perspective: Color,
+    coordinates: bool,
+    highlights: BitBoard,
+}
+
+impl BoardRenderer {
+    pub fn new() -> Self {
+        Self {
+            piece_style: PieceStyle::Figurines,
+            ansi_colors: false,
+            perspective: Color::White,
+            coordinates: true,
+            highlights: EMPTY_BITBOARD,
+        }
+    }
+
+    pub fn get_piece_style(&self) -> PieceStyle {
+        self.piece_style
+    }
+
+    pub fn set_piece_style(mut self, piece_style: PieceStyle) -> Self {
+        self.piece_style = piece_style;
+        self
+    }
+
+    pub fn get_ansi_colors(&self) -> bool {
+        self.ansi_colors
+    }
+
+    /// Colors the squares and pieces with ANSI escape codes.
+    pub fn set_ansi_colors(mut self, ansi_colors: bool) -> Self {
+        self.ansi_colors = ansi_colors;
+        self
+    }
+
+    pub fn get_perspective(&self) -> Color {
+        self.perspective
+    }
+
+    /// Renders the board from `perspective`'s side, so Black sees their pieces at the bottom.
+    pub fn set_perspective(mut self, perspective: Color) -> Self {
+        self.perspective = perspective;
+        self
+    }
+
+    pub fn get_coordinates(&self) -> bool {
+        self.coordinates
+    }
+
+    pub fn set_coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    pub fn get_highlights(&self) -> BitBoard {
+        self.highlights
+    }
+
+    /// Highlights `highlights`, e.g. the last move or a king in check. Highlighted squares get a
+    /// distinct background with ANSI colors and a `*` marker without.
+    pub fn set_highlights(mut self, highlights: BitBoard) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    pub fn highlight(mut self, square: Square) -> Self {
+        self.highlights |= BitBoard::from_square(square);
+        self
+    }
+
+    /// Returns a value that renders `board` with these settings when displayed.
+    pub fn display<'a>(&'a self, board: &'a Board) -> RenderedBoard<'a> {
+        RenderedBoard { renderer: self, board }
+    }
+
+    /// Writes `board` to `writer` with these settings.
+    pub fn write<W: fmt::Write>(&self, board: &Board, writer: &mut W) -> fmt::Result {
+        let flipped = self.perspective == Color::Black;
+
+        if self.coordinates {
+            Self::write_file_labels(writer, flipped)?;
+            writeln!(writer)?;
+        }
+        writeln!(writer, " -----------------")?;
+
+        for row in 0..NUM_RANKS {
+            let rank = Rank::from_index(if flipped { row } else { NUM_RANKS - 1 - row });
+            if self.coordinates {
+                write!(writer, "{}|", rank.to_index() + 1)?;
+            } else {
+                write!(writer, " |")?;
+            }
+
+            for column in 0..NUM_FILES {
+                let file = File::from_index(if flipped { NUM_FILES - 1 - column } else { column });
+                self.write_square(board, Square::create_square(rank, file), writer)?;
+            }
+
+            if self.ansi_colors {
+                writer.write_str(ANSI_RESET)?;
+            }
+            writeln!(writer, "|")?;
+        }
+
+        write!(writer, " -----------------")?;
+        if self.coordinates {
+            writeln!(writer)?;
+            Self::write_file_labels(writer, flipped)?;
+        }
+        Ok(())
+    }
+
+    fn write_file_labels<W: fmt::Write>(writer: &mut W, flipped: bool) -> fmt::Result {
+        write!(writer, " ")?;
+        for column in 0..NUM_FILES {
+            let file = if flipped { NUM_FILES - 1 - column } else { column };
+            write!(writer, " {}", (b'a' + file as u8) as char)?;
+        }
+        Ok(())
+    }
+
+    fn write_square<W: fmt::Write>(&self, board: &Board, square: Square, writer: &mut W) -> fmt::Result {
+        let highlighted = self.highlights.contains(square);
+        let piece = board.get_piece_at(square).map(|(piece, color)| {
+            let c = match self.piece_style {
+                PieceStyle::Letters => piece.to_char(color),
+                PieceStyle::Figurines => piece.to_fancy_char(color),
+            };
+            (c, color)
+        });
+
+        if self.ansi_colors {
+            let background = if highlighted {
+                ANSI_HIGHLIGHT
+            } else if square.is_light() {
+                ANSI_LIGHT_SQUARE
+            } else {
+                ANSI_DARK_SQUARE
+            };
+            writer.write_str(background)?;
+            match piece {
+                Some((c, Color::White)) => write!(writer, " {}{}", ANSI_WHITE_PIECE, c),
+                Some((c, Color::Black)) => write!(writer, " {}{}", ANSI_BLACK_PIECE, c),
+                None => write!(writer, "  "),
+            }
+        } else {
+            writer.write_char(if highlighted { HIGHLIGHT_MARKER } else { ' ' })?;
+            writer.write_char(piece.map_or('.', |(c, _)| c))
+        }
+    }
+}
+
+impl Default for BoardRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A board paired with the settings to render it, see [`BoardRenderer::display`].
+pub struct RenderedBoard<'a> {
+    renderer: &'a BoardRenderer,
+    board: &'a Board,
+}
+
+impl fmt::Display for RenderedBoard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.renderer.write(self.board, f)
+    }
+}
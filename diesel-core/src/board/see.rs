@@ -0,0 +1,71 @@
+use crate::bitboard::BitBoard;
+use crate::board::Board;
+use crate::board::movegen::pawn_push;
+use crate::chess_move::ChessMove;
+use crate::piece::{ALL_PIECES, Piece};
+
+// The king is worth more than everything else, so it is never traded
+const SEE_PIECE_VALUES: [i32; 6] = [100, 300, 300, 500, 900, 20000];
+
+const fn see_value(piece: Piece) -> i32 {
+    SEE_PIECE_VALUES[piece as usize]
+}
+
+impl Board {
+    /// Returns the material the side to move wins or loses from the exchange that `chess_move` starts
+    /// on its destination square, assuming both sides always recapture with their least valuable piece.
+    pub fn see(&self, chess_move: ChessMove) -> i32 {
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+        let (mover, us) = self.get_piece_at(from).expect("no piece to move");
+        let mut occupancy = self.get_occupancy_bitboard() - BitBoard::from_square(from);
+
+        // Every piece can be captured at most once, so 32 entries are enough
+        let mut gains = [0; 32];
+        gains[0] = match self.get_piece_at(to) {
+            Some((captured, _)) => see_value(captured),
+            None if mover == Piece::Pawn && Some(to) == self.en_passant_square => {
+                let captured = pawn_push(to, !us).expect("the captured pawn is on the board");
+                occupancy -= BitBoard::from_square(captured);
+                see_value(Piece::Pawn)
+            }
+            None => 0,
+        };
+        let mut on_square = see_value(mover);
+        if let Some(promotion) = chess_move.get_promotion() {
+            gains[0] += see_value(promotion) - see_value(Piece::Pawn);
+            on_square = see_value(promotion);
+        }
+
+        // Attackers are recomputed from the shrinking occupancy, which uncovers sliders behind them
+        let mut side = !us;
+        let mut depth = 0;
+        loop {
+            let attackers = self.get_attackers_to(to, occupancy) & occupancy;
+            let ours = attackers & self.get_pieces_bitboard(side);
+            let Some((piece, square)) = ALL_PIECES
+                .iter()
+                .find_map(|&piece| (ours & self.get_piece_bitboard(piece, side)).lsb().map(|square| (piece, square)))
+            else {
+                break;
+            };
+            // The king can only recapture when nothing defends the square
+            if piece == Piece::King && !(attackers & self.get_pieces_bitboard(!side)).is_empty() {
+                break;
+            }
+
+            depth += 1;
+            gains[depth] = on_square - gains[depth - 1];
+            on_square = see_value(piece);
+            occupancy -= BitBoard::from_square(square);
+            side = !side;
+        }
+
+        // Either side may stop capturing when continuing would lose material
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
+        }
+        gains[0]
+    }
+}
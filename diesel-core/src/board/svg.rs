@@ -0,0 +1,326 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::bitboard::{BitBoard, EMPTY_BITBOARD};
+use crate::board::Board;
+use crate::color::{ALL_COLORS, Color, NUM_COLORS};
+use crate::piece::{ALL_PIECES, NUM_PIECES, Piece};
+use crate::square::{ALL_SQUARES, Square};
+
+// The piece glyphs are drawn in a box of this size, so squares need no scaling
+const SQUARE_SIZE: f64 = 45.0;
+const MARGIN: f64 = 20.0;
+const CAPTION_HEIGHT: f64 = 30.0;
+
+const LIGHT_SQUARE: &str = "#f0d9b5";
+const DARK_SQUARE: &str = "#b58863";
+const HIGHLIGHT: &str = "#9bc700";
+const ARROW: &str = "#15781b";
+const ARROW_WIDTH: f64 = 8.0;
+const ARROW_HEAD_LENGTH: f64 = 20.0;
+const ARROW_HEAD_WIDTH: f64 = 22.0;
+
+/// Renders a board as a standalone SVG diagram. Piece glyphs are embedded, so the output needs no
+/// fonts or other assets beyond a sans-serif font for the coordinates and caption.
+#[derive(Clone, Debug)]
+pub struct SvgRenderer {
+    perspective: Color,
+    coordinates: bool,
+    highlights: BitBoard,
+    arrows: Vec<(Square, Square)>,
+    caption: Option<String>,
+}
+
+impl SvgRenderer {
+    pub fn new() -> Self {
+        Self {
+            perspective: Color::White,
+            coordinates: true,
+            highlights: EMPTY_BITBOARD,
+            arrows: Vec::new(),
+            caption: None,
+        }
+    }
+
+    pub fn get_perspective(&self) -> Color {
+        self.perspective
+    }
+
+    /// Draws the board from `perspective`'s side, so Black sees their pieces at the bottom.
+    pub fn set_perspective(mut self, perspective: Color) -> Self {
+        self.perspective = perspective;
+        self
+    }
+
+    pub fn get_coordinates(&self) -> bool {
+        self.coordinates
+    }
+
+    pub fn set_coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    pub fn get_highlights(&self) -> BitBoard {
+        self.highlights
+    }
+
+    pub fn set_highlights(mut self, highlights: BitBoard) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    pub fn highlight(mut self, square: Square) -> Self {
+        self.highlights |= BitBoard::from_square(square);
+        self
+    }
+
+    pub fn get_arrows(&self) -> &[(Square, Square)] {
+        &self.arrows
+    }
+
+    /// Adds an arrow pointing from `from` to `to`, drawn above the pieces.
+    pub fn arrow(mut self, from: Square, to: Square) -> Self {
+        self.arrows.push((from, to));
+        self
+    }
+
+    pub fn get_caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    /// Sets a caption drawn below the board.
+    pub fn set_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Returns the SVG document for `board`.
+    pub fn render(&self, board: &Board) -> String {
+        let mut svg = String::new();
+        self.write(board, &mut svg).expect("writing to a String cannot fail");
+        svg
+    }
+
+    fn write(&self, board: &Board, svg: &mut String) -> core::fmt::Result {
+        let margin = if self.coordinates { MARGIN } else { 0.0 };
+        let width = 8.0 * SQUARE_SIZE + 2.0 * margin;
+        let caption_height = if self.caption.is_some() { CAPTION_HEIGHT } else { 0.0 };
+        let height = width + caption_height;
+
+        writeln!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"##
+        )?;
+        self.write_glyph_defs(board, svg)?;
+        writeln!(svg, r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##)?;
+
+        for square in ALL_SQUARES {
+            let (x, y) = self.square_origin(square, margin);
+            let fill = if square.is_light() { LIGHT_SQUARE } else { DARK_SQUARE };
+            writeln!(svg, r##"<rect x="{x}" y="{y}" width="{SQUARE_SIZE}" height="{SQUARE_SIZE}" fill="{fill}"/>"##)?;
+        }
+        for square in self.highlights {
+            let (x, y) = self.square_origin(square, margin);
+            writeln!(svg, r##"<rect x="{x}" y="{y}" width="{SQUARE_SIZE}" height="{SQUARE_SIZE}" fill="{HIGHLIGHT}" fill-opacity="0.5"/>"##)?;
+        }
+
+        for square in ALL_SQUARES {
+            if let Some((piece, color)) = board.get_piece_at(square) {
+                let (x, y) = self.square_origin(square, margin);
+                writeln!(svg, r##"<use xlink:href="#{}" x="{x}" y="{y}"/>"##, glyph_id(piece, color))?;
+            }
+        }
+
+        for &(from, to) in &self.arrows {
+            self.write_arrow(from, to, margin, svg)?;
+        }
+
+        if self.coordinates {
+            self.write_coordinates(svg)?;
+        }
+
+        if let Some(caption) = &self.caption {
+            write!(
+                svg,
+                r##"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="middle" font-family="sans-serif" font-size="16" fill="#000000">"##,
+                width / 2.0,
+                width + caption_height / 2.0
+            )?;
+            write_escaped(caption, svg)?;
+            writeln!(svg, "</text>")?;
+        }
+
+        writeln!(svg, "</svg>")
+    }
+
+    // Only the glyphs of pieces on the board are defined, which keeps sparse diagrams small
+    fn write_glyph_defs(&self, board: &Board, svg: &mut String) -> core::fmt::Result {
+        let mut used = [[false; NUM_PIECES]; NUM_COLORS];
+        for square in ALL_SQUARES {
+            if let Some((piece, color)) = board.get_piece_at(square) {
+                used[color.to_index()][piece.to_index()] = true;
+            }
+        }
+
+        writeln!(svg, "<defs>")?;
+        for color in ALL_COLORS {
+            let (fill, detail) = match color {
+                Color::White => ("#ffffff", "#000000"),
+                Color::Black => ("#000000", "#ffffff"),
+            };
+            for piece in ALL_PIECES {
+                if !used[color.to_index()][piece.to_index()] {
+                    continue;
+                }
+                let (body, details) = glyph(piece);
+                write!(
+                    svg,
+                    r##"<g id="{}" fill="{fill}" stroke="#000000" stroke-width="1.5" stroke-linejoin="round">{body}"##,
+                    glyph_id(piece, color)
+                )?;
+                if !details.is_empty() {
+                    write!(svg, r##"<path d="{details}" fill="none" stroke="{detail}"/>"##)?;
+                }
+                writeln!(svg, "</g>")?;
+            }
+        }
+        writeln!(svg, "</defs>")
+    }
+
+    fn write_arrow(&self, from: Square, to: Square, margin: f64, svg: &mut String) -> core::fmt::Result {
+        let (from_x, from_y) = self.square_center(from, margin);
+        let (to_x, to_y) = self.square_center(to, margin);
+        let length = sqrt((to_x - from_x) * (to_x - from_x) + (to_y - from_y) * (to_y - from_y));
+        if length == 0.0 {
+            return Ok(());
+        }
+        let (unit_x, unit_y) = ((to_x - from_x) / length, (to_y - from_y) / length);
+
+        // The shaft stops where the head starts, so its square end can't poke out past the sides of the head
+        let base_x = to_x - unit_x * ARROW_HEAD_LENGTH;
+        let base_y = to_y - unit_y * ARROW_HEAD_LENGTH;
+        let (side_x, side_y) = (-unit_y * ARROW_HEAD_WIDTH / 2.0, unit_x * ARROW_HEAD_WIDTH / 2.0);
+
+        writeln!(
+            svg,
+            r##"<g fill="{ARROW}" stroke="{ARROW}" opacity="0.8"><line x1="{from_x}" y1="{from_y}" x2="{base_x:.2}" y2="{base_y:.2}" stroke-width="{ARROW_WIDTH}"/><polygon points="{to_x},{to_y} {:.2},{:.2} {:.2},{:.2}" stroke="none"/></g>"##,
+            base_x + side_x,
+            base_y + side_y,
+            base_x - side_x,
+            base_y - side_y
+        )
+    }
+
+    fn write_coordinates(&self, svg: &mut String) -> core::fmt::Result {
+        for i in 0..8 {
+            let (file, rank) = match self.perspective {
+                Color::White => (i, 7 - i),
+                Color::Black => (7 - i, i),
+            };
+            let center = MARGIN + (i as f64 + 0.5) * SQUARE_SIZE;
+            writeln!(
+                svg,
+                r##"<text x="{center}" y="{}" text-anchor="middle" dominant-baseline="middle" font-family="sans-serif" font-size="12" fill="#000000">{}</text>"##,
+                MARGIN + 8.0 * SQUARE_SIZE + MARGIN / 2.0,
+                (b'a' + file as u8) as char
+            )?;
+            writeln!(
+                svg,
+                r##"<text x="{}" y="{center}" text-anchor="middle" dominant-baseline="middle" font-family="sans-serif" font-size="12" fill="#000000">{}</text>"##,
+                MARGIN / 2.0,
+                rank + 1
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the top left corner of `square` in the diagram.
+    fn square_origin(&self, square: Square, margin: f64) -> (f64, f64) {
+        let file = square.get_file().to_index();
+        let rank = square.get_rank().to_index();
+        let (column, row) = match self.perspective {
+            Color::White => (file, 7 - rank),
+            Color::Black => (7 - file, rank),
+        };
+        (margin + column as f64 * SQUARE_SIZE, margin + row as f64 * SQUARE_SIZE)
+    }
+
+    fn square_center(&self, square: Square, margin: f64) -> (f64, f64) {
+        let (x, y) = self.square_origin(square, margin);
+        (x + SQUARE_SIZE / 2.0, y + SQUARE_SIZE / 2.0)
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn glyph_id(piece: Piece, color: Color) -> String {
+    let color = match color {
+        Color::White => 'w',
+        Color::Black => 'b',
+    };
+    alloc::format!("{}{}", color, piece.to_char(Color::Black))
+}
+
+// Returns the filled outline of a piece and the detail lines drawn on top, both in a 45x45 box
+fn glyph(piece: Piece) -> (&'static str, &'static str) {
+    match piece {
+        Piece::Pawn => (
+            r##"<circle cx="22.5" cy="14" r="5.5"/><path d="M 17 34 L 19 21 L 26 21 L 28 34 Z"/><rect x="12" y="33" width="21" height="5" rx="1"/>"##,
+            "",
+        ),
+        Piece::Knight => (
+            r##"<path d="M 14 38 L 32 38 L 31 26 C 33 19 30 11 22 8.5 L 20 5 L 17.5 10 C 13.5 12.5 10.5 17 9.5 23 L 12.5 26 L 17 22.5 L 21 23.5 C 16.5 27.5 14 32 14 38 Z"/>"##,
+            "M 16.5 15 L 18.5 15",
+        ),
+        Piece::Bishop => (
+            r##"<circle cx="22.5" cy="7.5" r="2.5"/><path d="M 22.5 10 C 16 14 14 21 16 27 L 29 27 C 31 21 29 14 22.5 10 Z"/><rect x="15" y="27" width="15" height="4" rx="1"/><rect x="11" y="33" width="23" height="5" rx="1"/>"##,
+            "M 20 18 L 25 18 M 22.5 15.5 L 22.5 20.5",
+        ),
+        Piece::Rook => (
+            r##"<path d="M 11 14 L 11 9 L 15 9 L 15 11 L 20 11 L 20 9 L 25 9 L 25 11 L 30 11 L 30 9 L 34 9 L 34 14 Z"/><path d="M 14 14 L 31 14 L 30 31 L 15 31 Z"/><rect x="10" y="31" width="25" height="6" rx="1"/>"##,
+            "M 15 17 L 30 17 M 15 28 L 30 28",
+        ),
+        Piece::Queen => (
+            r##"<path d="M 9 13 L 14 30 L 16 11 L 20 28 L 22.5 9 L 25 28 L 29 11 L 31 30 L 36 13 L 32 31 L 13 31 Z"/><circle cx="9" cy="12" r="2"/><circle cx="16" cy="10" r="2"/><circle cx="22.5" cy="8" r="2"/><circle cx="29" cy="10" r="2"/><circle cx="36" cy="12" r="2"/><rect x="11" y="31" width="23" height="6" rx="1"/>"##,
+            "M 13 31 L 32 31",
+        ),
+        Piece::King => (
+            r##"<path d="M 22.5 13 C 19 13 17 16 18 19 L 12 17 C 8 21 10 27 14 30 L 31 30 C 35 27 37 21 33 17 L 27 19 C 28 16 26 13 22.5 13 Z"/><rect x="11" y="31" width="23" height="6" rx="1"/><path d="M 22.5 4 L 22.5 12 M 19 7.5 L 26 7.5" fill="none"/>"##,
+            "M 14 26 L 31 26",
+        ),
+    }
+}
+
+// `f64::sqrt` needs std. Newton's method from above converges in a few steps for board-sized lengths
+fn sqrt(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut root = x.max(1.0);
+    loop {
+        let next = (root + x / root) / 2.0;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+fn write_escaped(text: &str, svg: &mut String) -> core::fmt::Result {
+    for c in text.chars() {
+        match c {
+            '&' => svg.write_str("&amp;")?,
+            '<' => svg.write_str("&lt;")?,
+            '>' => svg.write_str("&gt;")?,
+            '"' => svg.write_str("&quot;")?,
+            c => svg.write_char(c)?,
+        }
+    }
+    Ok(())
+}
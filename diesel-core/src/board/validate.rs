@@ -0,0 +1,126 @@
+use crate::bitboard::{BitBoard, EMPTY_BITBOARD};
+use crate::board::Board;
+use crate::castling::{BLACK_KING_SIDE, BLACK_QUEEN_SIDE, WHITE_KING_SIDE, WHITE_QUEEN_SIDE};
+use crate::color::{ALL_COLORS, Color};
+use crate::piece::{ALL_PIECES, Piece};
+use crate::rank::Rank;
+use crate::square::{ALL_SQUARES, Square, A1, A8, E1, E8, H1, H8};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A side bitboard differs from the union of that side's piece bitboards
+    SideBitboardMismatch(Color),
+    /// The occupancy bitboard differs from the union of the side bitboards
+    OccupancyMismatch,
+    /// More than one piece bitboard has this square set
+    OverlappingPieces(Square),
+    /// The mailbox disagrees with the bitboards on this square
+    MailboxMismatch(Square),
+    /// A castling right is set without the king and rook on their starting squares
+    CastlingRightsMismatch(u8),
+    /// The en passant square is not on the third or sixth rank behind a pawn that just double pushed
+    InvalidEnPassantSquare(Square),
+    /// The stored hash differs from a full recomputation
+    HashMismatch { stored: u64, computed: u64 },
+}
+
+const CASTLING_SQUARES: [(u8, Color, Square, Square); 4] = [
+    (WHITE_KING_SIDE, Color::White, E1, H1),
+    (WHITE_QUEEN_SIDE, Color::White, E1, A1),
+    (BLACK_KING_SIDE, Color::Black, E8, H8),
+    (BLACK_QUEEN_SIDE, Color::Black, E8, A8),
+];
+
+impl Board {
+    /// Checks that the redundant parts of the board agree with each other, returning the first
+    /// violation found. Intended for debug builds, since it recomputes the hash from scratch.
+    pub fn validate_invariants(&self) -> Result<(), InvariantViolation> {
+        self.validate_bitboards()?;
+        self.validate_mailbox()?;
+        self.validate_castling_rights()?;
+        self.validate_en_passant_square()?;
+
+        let computed = self.compute_hash();
+        if self.hash != computed {
+            return Err(InvariantViolation::HashMismatch { stored: self.hash, computed });
+        }
+        Ok(())
+    }
+
+    fn validate_bitboards(&self) -> Result<(), InvariantViolation> {
+        let mut seen = EMPTY_BITBOARD;
+        for color in ALL_COLORS {
+            let mut side = EMPTY_BITBOARD;
+            for piece in ALL_PIECES {
+                let bitboard = self.piece_bitboards[color.to_index()][piece.to_index()];
+                if let Some(square) = (seen & bitboard).lsb() {
+                    return Err(InvariantViolation::OverlappingPieces(square));
+                }
+                seen |= bitboard;
+                side |= bitboard;
+            }
+            if self.side_bitboards[color.to_index()] != side {
+                return Err(InvariantViolation::SideBitboardMismatch(color));
+            }
+        }
+
+        let occupancy: BitBoard = self.side_bitboards[Color::White.to_index()] | self.side_bitboards[Color::Black.to_index()];
+        if self.occupancy_bitboard != occupancy {
+            return Err(InvariantViolation::OccupancyMismatch);
+        }
+        Ok(())
+    }
+
+    fn validate_mailbox(&self) -> Result<(), InvariantViolation> {
+        for square in ALL_SQUARES {
+            let from_bitboards = ALL_COLORS.into_iter().find_map(|color| {
+                ALL_PIECES
+                    .into_iter()
+                    .find(|piece| self.piece_bitboards[color.to_index()][piece.to_index()].contains(square))
+                    .map(|piece| (piece, color))
+            });
+            if self.mailbox[square.to_index()] != from_bitboards {
+                return Err(InvariantViolation::MailboxMismatch(square));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_castling_rights(&self) -> Result<(), InvariantViolation> {
+        for (right, color, king_square, rook_square) in CASTLING_SQUARES {
+            if self.castling_rights.has(right)
+                && (self.get_piece_at(king_square) != Some((Piece::King, color))
+                    || self.get_piece_at(rook_square) != Some((Piece::Rook, color)))
+            {
+                return Err(InvariantViolation::CastlingRightsMismatch(right));
+            }
+        }
+        Ok(())
+    }
+
+    // The square must be empty, on the side to move's sixth rank, with the opponent's pawn in front
+    // of it and its starting square empty
+    pub(crate) fn validate_en_passant_square(&self) -> Result<(), InvariantViolation> {
+        let Some(square) = self.en_passant_square else {
+            return Ok(());
+        };
+        let mover = !self.to_move;
+        let pawn_square = match mover {
+            Color::White => square.up(),
+            Color::Black => square.down(),
+        };
+        let start_square = match mover {
+            Color::White => square.down(),
+            Color::Black => square.up(),
+        };
+
+        let valid = square.relative_rank(self.to_move) == Rank::Sixth
+            && self.get_piece_at(square).is_none()
+            && pawn_square.is_some_and(|pawn_square| self.get_piece_at(pawn_square) == Some((Piece::Pawn, mover)))
+            && start_square.is_some_and(|start_square| self.get_piece_at(start_square).is_none());
+        if !valid {
+            return Err(InvariantViolation::InvalidEnPassantSquare(square));
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-use std::ops::{BitOr, BitOrAssign};
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+use core::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct CastlingRights(u8);
@@ -8,6 +10,8 @@ pub const WHITE_QUEEN_SIDE: u8 = 0b00000010;
 pub const BLACK_KING_SIDE: u8 = 0b00000100;
 pub const BLACK_QUEEN_SIDE: u8 = 0b00001000;
 
+pub const NUM_CASTLING_RIGHTS: usize = 4;
+
 pub const NO_CASTLING: u8 = 0b00000000;
 pub const WHITE_CASTLING: u8 = WHITE_KING_SIDE | WHITE_QUEEN_SIDE;
 pub const BLACK_CASTLING: u8 = BLACK_KING_SIDE | BLACK_QUEEN_SIDE;
@@ -20,6 +24,23 @@ impl CastlingRights {
     pub fn from_bytes(bytes: u8) -> Self {
         Self(bytes)
     }
+
+    pub fn to_bytes(&self) -> u8 {
+        self.0
+    }
+
+    pub fn has(&self, rights: u8) -> bool {
+        self.0 & rights == rights
+    }
+
+    pub fn remove(&mut self, rights: u8) {
+        self.0 &= !rights;
+    }
+
+    /// Swaps White's rights with Black's.
+    pub fn flip(&self) -> Self {
+        Self(((self.0 & WHITE_CASTLING) << 2) | ((self.0 & BLACK_CASTLING) >> 2))
+    }
 }
 
 impl BitOr for CastlingRights {
@@ -34,4 +55,42 @@ impl BitOrAssign for CastlingRights {
     fn bitor_assign(&mut self, rhs: Self) {
         self.0 |= rhs.0;
     }
+}
+
+const CASTLING_CHARS: [(u8, char); NUM_CASTLING_RIGHTS] = [
+    (WHITE_KING_SIDE, 'K'),
+    (WHITE_QUEEN_SIDE, 'Q'),
+    (BLACK_KING_SIDE, 'k'),
+    (BLACK_QUEEN_SIDE, 'q'),
+];
+
+// FEN castling field, e.g. "KQkq", or "-" when neither side can castle
+impl fmt::Display for CastlingRights {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 == NO_CASTLING {
+            return write!(f, "-");
+        }
+        for (right, c) in CASTLING_CHARS {
+            if self.has(right) {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CastlingRights {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            return Ok(Self::empty());
+        }
+        let mut castling_rights = Self::empty();
+        for c in s.chars() {
+            let (right, _) = CASTLING_CHARS.iter().find(|(_, right_char)| *right_char == c).ok_or(())?;
+            castling_rights |= Self::from_bytes(*right);
+        }
+        Ok(castling_rights)
+    }
 }
\ No newline at end of file
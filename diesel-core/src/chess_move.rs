@@ -1,5 +1,8 @@
+use core::fmt;
+use core::str::FromStr;
+
 use crate::square::Square;
-use crate::piece::Piece;
+use crate::piece::{ALL_PIECES, Piece};
 
 #[derive(Clone, Copy, Eq, PartialOrd, PartialEq, Default, Debug, Hash)]
 pub struct ChessMove {
@@ -25,4 +28,52 @@ impl ChessMove {
         self.promotion
     }
 
-}
\ No newline at end of file
+    /// Packs the move as `from | to << 6 | promotion << 12`, where promotion is the piece index + 1 or 0 for none.
+    pub fn to_u16(&self) -> u16 {
+        let promotion = self.promotion.map_or(0, |piece| piece.to_index() as u16 + 1);
+        self.from.to_int() as u16 | (self.to.to_int() as u16) << 6 | promotion << 12
+    }
+
+    /// Unpacks a move packed with [`ChessMove::to_u16`], or returns `None` if the promotion bits are invalid.
+    pub fn from_u16(bits: u16) -> Option<Self> {
+        let promotion = match bits >> 12 {
+            0 => None,
+            index => Some(*ALL_PIECES.get(index as usize - 1)?),
+        };
+        let from = Square::new((bits & 0x3F) as u8);
+        let to = Square::new((bits >> 6 & 0x3F) as u8);
+        Some(ChessMove::new(from, to, promotion))
+    }
+
+}
+
+// UCI long algebraic notation, e.g. "e2e4" or "e7e8q"
+impl fmt::Display for ChessMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+        if let Some(promotion) = self.promotion {
+            write!(f, "{}", promotion)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ChessMove {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.is_ascii() || (s.len() != 4 && s.len() != 5) {
+            return Err(());
+        }
+        let from = s[0..2].parse::<Square>()?;
+        let to = s[2..4].parse::<Square>()?;
+        let promotion = match s[4..].chars().next() {
+            Some(c) => match Piece::from_char(c)? {
+                Piece::Pawn | Piece::King => return Err(()),
+                piece => Some(piece),
+            },
+            None => None,
+        };
+        Ok(Self::new(from, to, promotion))
+    }
+}
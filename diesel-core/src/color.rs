@@ -1,5 +1,5 @@
-use std::ops::Not;
-use std::str::FromStr;
+use core::ops::Not;
+use core::str::FromStr;
 
 #[derive(PartialOrd, PartialEq, Eq, Copy, Clone, Debug, Hash)]
 pub enum Color {
@@ -12,7 +12,7 @@ pub const NUM_COLORS: usize = 2;
 pub const ALL_COLORS: [Color; NUM_COLORS] = [Color::White, Color::Black];
 
 impl Color {
-    pub fn to_index(&self) -> usize {
+    pub const fn to_index(&self) -> usize {
         *self as usize
     }
 
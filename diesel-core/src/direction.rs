@@ -0,0 +1,96 @@
+#[derive(PartialEq, Eq, Ord, PartialOrd, Copy, Clone, Debug, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthNorthEast,
+    EastNorthEast,
+    EastSouthEast,
+    SouthSouthEast,
+    SouthSouthWest,
+    WestSouthWest,
+    WestNorthWest,
+    NorthNorthWest,
+}
+
+pub const NUM_RAY_DIRECTIONS: usize = 8;
+pub const NUM_KNIGHT_DIRECTIONS: usize = 8;
+pub const NUM_DIRECTIONS: usize = NUM_RAY_DIRECTIONS + NUM_KNIGHT_DIRECTIONS;
+
+pub const ALL_RAY_DIRECTIONS: [Direction; NUM_RAY_DIRECTIONS] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+pub const ALL_KNIGHT_DIRECTIONS: [Direction; NUM_KNIGHT_DIRECTIONS] = [
+    Direction::NorthNorthEast,
+    Direction::EastNorthEast,
+    Direction::EastSouthEast,
+    Direction::SouthSouthEast,
+    Direction::SouthSouthWest,
+    Direction::WestSouthWest,
+    Direction::WestNorthWest,
+    Direction::NorthNorthWest,
+];
+
+impl Direction {
+    /// Returns the `(file_offset, rank_offset)` of a single step in this direction.
+    pub const fn get_offset(&self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, -1),
+            Direction::South => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, 1),
+            Direction::NorthNorthEast => (1, 2),
+            Direction::EastNorthEast => (2, 1),
+            Direction::EastSouthEast => (2, -1),
+            Direction::SouthSouthEast => (1, -2),
+            Direction::SouthSouthWest => (-1, -2),
+            Direction::WestSouthWest => (-2, -1),
+            Direction::WestNorthWest => (-2, 1),
+            Direction::NorthNorthWest => (-1, 2),
+        }
+    }
+
+    /// Returns the change in square index of a single step, ignoring the edges of the board.
+    pub const fn get_delta(&self) -> i8 {
+        let (file_offset, rank_offset) = self.get_offset();
+        (rank_offset * 8 + file_offset) as i8
+    }
+
+    pub const fn opposite(&self) -> Direction {
+        if self.is_knight() {
+            ALL_KNIGHT_DIRECTIONS[(self.to_index() - NUM_RAY_DIRECTIONS + NUM_KNIGHT_DIRECTIONS / 2) % NUM_KNIGHT_DIRECTIONS]
+        } else {
+            ALL_RAY_DIRECTIONS[(self.to_index() + NUM_RAY_DIRECTIONS / 2) % NUM_RAY_DIRECTIONS]
+        }
+    }
+
+    pub const fn is_diagonal(&self) -> bool {
+        !self.is_knight() && self.to_index() % 2 == 1
+    }
+
+    /// `true` for the eight knight jumps.
+    pub const fn is_knight(&self) -> bool {
+        self.to_index() >= NUM_RAY_DIRECTIONS
+    }
+
+    pub const fn to_index(&self) -> usize {
+        *self as usize
+    }
+}
@@ -0,0 +1,322 @@
+use core::ops::{Add, AddAssign, Mul, Sub};
+
+use crate::bitboard::{BitBoard, EMPTY_BITBOARD};
+use crate::board::Board;
+use crate::color::Color;
+use crate::piece::{Piece, NUM_PIECES};
+use crate::rank::NUM_RANKS;
+use crate::square::{Square, NUM_SQUARES};
+use crate::tables::{
+    get_bishop_moves, get_king_moves, get_knight_moves, get_pawn_attacks, get_queen_moves, get_rook_moves,
+    ADJACENT_FILE_MASKS, FILE_MASKS, RANK_MASKS,
+};
+
+/// One value per piece, for move ordering and pruning decisions that don't need the full evaluation.
+pub const PIECE_VALUES: [i32; NUM_PIECES] = [100, 320, 330, 500, 900, 0];
+
+/// The phase of a position with every piece but the pawns and kings still on the board. The phase
+/// falls to 0 as pieces are traded, moving the evaluation from the midgame to the endgame terms.
+pub const MAX_PHASE: i32 = 24;
+const PHASE_WEIGHTS: [i32; NUM_PIECES] = [0, 1, 1, 2, 4, 0];
+
+// A midgame and an endgame score, blended by the phase once the whole position is scored
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Tapered {
+    midgame: i32,
+    endgame: i32,
+}
+
+const fn s(midgame: i32, endgame: i32) -> Tapered {
+    Tapered { midgame, endgame }
+}
+
+impl Add for Tapered {
+    type Output = Tapered;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        s(self.midgame + rhs.midgame, self.endgame + rhs.endgame)
+    }
+}
+
+impl AddAssign for Tapered {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Tapered {
+    type Output = Tapered;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        s(self.midgame - rhs.midgame, self.endgame - rhs.endgame)
+    }
+}
+
+impl Mul<i32> for Tapered {
+    type Output = Tapered;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        s(self.midgame * rhs, self.endgame * rhs)
+    }
+}
+
+const MATERIAL: [Tapered; NUM_PIECES] = [s(100, 120), s(320, 300), s(330, 320), s(500, 550), s(900, 950), s(0, 0)];
+
+// Piece-square tables from White's point of view, written with the eighth rank first
+#[rustfmt::skip]
+const PAWN_MIDGAME_TABLE: [i32; NUM_SQUARES] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+     30,  30,  30,  30,  30,  30,  30,  30,
+     10,  10,  20,  30,  30,  20,  10,  10,
+      5,   5,  10,  25,  25,  10,   5,   5,
+      0,   0,   0,  20,  20,   0,   0,   0,
+      5,  -5, -10,   0,   0, -10,  -5,   5,
+      5,  10,  10, -20, -20,  10,  10,   5,
+      0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const PAWN_ENDGAME_TABLE: [i32; NUM_SQUARES] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+     60,  60,  60,  60,  60,  60,  60,  60,
+     40,  40,  40,  40,  40,  40,  40,  40,
+     25,  25,  25,  25,  25,  25,  25,  25,
+     15,  15,  15,  15,  15,  15,  15,  15,
+      5,   5,   5,   5,   5,   5,   5,   5,
+      0,   0,   0,   0,   0,   0,   0,   0,
+      0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; NUM_SQUARES] = [
+    -50, -40, -30, -30, -30, -30, -40, -50,
+    -40, -20,   0,   0,   0,   0, -20, -40,
+    -30,   0,  10,  15,  15,  10,   0, -30,
+    -30,   5,  15,  20,  20,  15,   5, -30,
+    -30,   0,  15,  20,  20,  15,   0, -30,
+    -30,   5,  10,  15,  15,  10,   5, -30,
+    -40, -20,   0,   5,   5,   0, -20, -40,
+    -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; NUM_SQUARES] = [
+    -20, -10, -10, -10, -10, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,  10,  10,   5,   0, -10,
+    -10,   5,   5,  10,  10,   5,   5, -10,
+    -10,   0,  10,  10,  10,  10,   0, -10,
+    -10,  10,  10,  10,  10,  10,  10, -10,
+    -10,   5,   0,   0,   0,   0,   5, -10,
+    -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; NUM_SQUARES] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+      5,  10,  10,  10,  10,  10,  10,   5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+     -5,   0,   0,   0,   0,   0,   0,  -5,
+      0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; NUM_SQUARES] = [
+    -20, -10, -10,  -5,  -5, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,   5,   5,   5,   0, -10,
+     -5,   0,   5,   5,   5,   5,   0,  -5,
+      0,   0,   5,   5,   5,   5,   0,  -5,
+    -10,   5,   5,   5,   5,   5,   0, -10,
+    -10,   0,   5,   0,   0,   0,   0, -10,
+    -20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_MIDGAME_TABLE: [i32; NUM_SQUARES] = [
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -20, -30, -30, -40, -40, -30, -30, -20,
+    -10, -20, -20, -20, -20, -20, -20, -10,
+     20,  20,   0,   0,   0,   0,  20,  20,
+     20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_ENDGAME_TABLE: [i32; NUM_SQUARES] = [
+    -50, -40, -30, -20, -20, -30, -40, -50,
+    -30, -20, -10,   0,   0, -10, -20, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -30,   0,   0,   0,   0, -30, -30,
+    -50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+// Only pawns and kings play differently enough in the endgame to need their own tables
+const MIDGAME_TABLES: [&[i32; NUM_SQUARES]; NUM_PIECES] =
+    [&PAWN_MIDGAME_TABLE, &KNIGHT_TABLE, &BISHOP_TABLE, &ROOK_TABLE, &QUEEN_TABLE, &KING_MIDGAME_TABLE];
+const ENDGAME_TABLES: [&[i32; NUM_SQUARES]; NUM_PIECES] =
+    [&PAWN_ENDGAME_TABLE, &KNIGHT_TABLE, &BISHOP_TABLE, &ROOK_TABLE, &QUEEN_TABLE, &KING_ENDGAME_TABLE];
+
+// Pawn structure, with the rank bonuses indexed by the pawn's rank relative to its side
+const DOUBLED_PAWN: Tapered = s(-10, -20);
+const ISOLATED_PAWN: Tapered = s(-12, -15);
+const PASSED_PAWN: [Tapered; NUM_RANKS] = [s(0, 0), s(5, 10), s(10, 20), s(15, 35), s(30, 60), s(50, 100), s(80, 150), s(0, 0)];
+const CONNECTED_PAWN: [Tapered; NUM_RANKS] = [s(0, 0), s(3, 2), s(5, 4), s(8, 6), s(12, 10), s(20, 18), s(35, 30), s(0, 0)];
+
+// Per safe square attacked beyond the number a piece typically has, indexed by piece
+const MOBILITY_WEIGHTS: [Tapered; NUM_PIECES] = [s(0, 0), s(4, 4), s(5, 5), s(2, 4), s(1, 2), s(0, 0)];
+const MOBILITY_BASELINES: [i32; NUM_PIECES] = [0, 4, 6, 6, 12, 0];
+
+const ROOK_OPEN_FILE: Tapered = s(25, 10);
+const ROOK_SEMI_OPEN_FILE: Tapered = s(12, 6);
+const BISHOP_PAIR: Tapered = s(30, 50);
+
+// Attack units for each square next to the enemy king a piece attacks. A lone attacker is rarely a
+// threat, and the danger grows with the square of the units up to a cap
+const KING_ATTACK_UNITS: [i32; NUM_PIECES] = [0, 2, 2, 3, 5, 0];
+const MIN_KING_ATTACKERS: i32 = 2;
+const MAX_KING_DANGER: i32 = 500;
+
+/// Scores the position in centipawns from the side to move's point of view.
+pub fn evaluate(board: &Board) -> i32 {
+    let score = evaluate_color(board, Color::White) - evaluate_color(board, Color::Black);
+    let phase = get_phase(board);
+    let white = (score.midgame * phase + score.endgame * (MAX_PHASE - phase)) / MAX_PHASE;
+    match board.get_to_move() {
+        Color::White => white,
+        Color::Black => -white,
+    }
+}
+
+/// Returns how much material besides pawns is left, from [`MAX_PHASE`] at the start down to 0.
+pub fn get_phase(board: &Board) -> i32 {
+    let phase: i32 = [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen]
+        .iter()
+        .map(|&piece| {
+            let count = board.get_piece_bitboard(piece, Color::White).count_bits() + board.get_piece_bitboard(piece, Color::Black).count_bits();
+            PHASE_WEIGHTS[piece.to_index()] * count as i32
+        })
+        .sum();
+    phase.min(MAX_PHASE)
+}
+
+fn evaluate_color(board: &Board, color: Color) -> Tapered {
+    evaluate_pieces(board, color) + evaluate_pawns(board, color)
+}
+
+// Material, placement, mobility, king attacks and the rook and bishop terms
+fn evaluate_pieces(board: &Board, color: Color) -> Tapered {
+    let occupancy = board.get_occupancy_bitboard();
+    let our_pawns = board.get_piece_bitboard(Piece::Pawn, color);
+    let all_pawns = our_pawns | board.get_piece_bitboard(Piece::Pawn, !color);
+    // Squares held by our own pieces or guarded by enemy pawns don't count towards mobility
+    let unsafe_squares = board.get_pieces_bitboard(color) | get_pawn_attacks_bitboard(board, !color);
+    let enemy_king_zone = board
+        .get_king_bitboard(!color)
+        .fold(EMPTY_BITBOARD, |zone, king| zone | BitBoard::from_square(king) | get_king_moves(king));
+
+    let mut score = Tapered::default();
+    let mut king_attackers = 0;
+    let mut king_attack_units = 0;
+    for square in board.get_pieces_bitboard(color) {
+        let Some((piece, _)) = board.get_piece_at(square) else {
+            continue;
+        };
+        let index = get_table_index(square, color);
+        score += MATERIAL[piece.to_index()] + s(MIDGAME_TABLES[piece.to_index()][index], ENDGAME_TABLES[piece.to_index()][index]);
+
+        let attacks = match piece {
+            Piece::Knight => get_knight_moves(square),
+            Piece::Bishop => get_bishop_moves(square, occupancy),
+            Piece::Rook => get_rook_moves(square, occupancy),
+            Piece::Queen => get_queen_moves(square, occupancy),
+            Piece::Pawn | Piece::King => continue,
+        };
+        let mobility = (attacks - unsafe_squares).count_bits() as i32;
+        score += MOBILITY_WEIGHTS[piece.to_index()] * (mobility - MOBILITY_BASELINES[piece.to_index()]);
+
+        let king_attacks = (attacks & enemy_king_zone).count_bits() as i32;
+        if king_attacks > 0 {
+            king_attackers += 1;
+            king_attack_units += KING_ATTACK_UNITS[piece.to_index()] * king_attacks;
+        }
+
+        if piece == Piece::Rook {
+            let file = FILE_MASKS[square.get_file().to_index()];
+            if (file & all_pawns).is_empty() {
+                score += ROOK_OPEN_FILE;
+            } else if (file & our_pawns).is_empty() {
+                score += ROOK_SEMI_OPEN_FILE;
+            }
+        }
+    }
+
+    if king_attackers >= MIN_KING_ATTACKERS {
+        score += s((king_attack_units * king_attack_units / 2).min(MAX_KING_DANGER), 0);
+    }
+    if board.get_piece_bitboard(Piece::Bishop, color).count_bits() >= 2 {
+        score += BISHOP_PAIR;
+    }
+    score
+}
+
+// Doubled, isolated, passed and connected pawns
+fn evaluate_pawns(board: &Board, color: Color) -> Tapered {
+    let our_pawns = board.get_piece_bitboard(Piece::Pawn, color);
+    let their_pawns = board.get_piece_bitboard(Piece::Pawn, !color);
+    let defended = get_pawn_attacks_bitboard(board, color);
+
+    let mut score = Tapered::default();
+    for square in our_pawns {
+        let file = square.get_file().to_index();
+        let rank = square.get_rank().to_index();
+        let relative_rank = square.relative_rank(color).to_index();
+        let ahead = get_forward_ranks(color, rank);
+
+        let doubled = !(FILE_MASKS[file] & ahead & our_pawns).is_empty();
+        if doubled {
+            score += DOUBLED_PAWN;
+        }
+        if (ADJACENT_FILE_MASKS[file] & our_pawns).is_empty() {
+            score += ISOLATED_PAWN;
+        }
+        // The rear pawn of a doubled pair is left to the front one
+        if !doubled && ((FILE_MASKS[file] | ADJACENT_FILE_MASKS[file]) & ahead & their_pawns).is_empty() {
+            score += PASSED_PAWN[relative_rank];
+        }
+        if defended.contains(square) || !(ADJACENT_FILE_MASKS[file] & RANK_MASKS[rank] & our_pawns).is_empty() {
+            score += CONNECTED_PAWN[relative_rank];
+        }
+    }
+    score
+}
+
+fn get_pawn_attacks_bitboard(board: &Board, color: Color) -> BitBoard {
+    board
+        .get_piece_bitboard(Piece::Pawn, color)
+        .fold(EMPTY_BITBOARD, |attacks, pawn| attacks | get_pawn_attacks(color, pawn))
+}
+
+// The ranks in front of `rank` from `color`'s point of view
+fn get_forward_ranks(color: Color, rank: usize) -> BitBoard {
+    match color {
+        Color::White => RANK_MASKS[rank].north_fill() - RANK_MASKS[rank],
+        Color::Black => RANK_MASKS[rank].south_fill() - RANK_MASKS[rank],
+    }
+}
+
+// The tables are written from White's side with the eighth rank first, so White's squares are flipped
+fn get_table_index(square: Square, color: Color) -> usize {
+    match color {
+        Color::White => square.flip().to_index(),
+        Color::Black => square.to_index(),
+    }
+}
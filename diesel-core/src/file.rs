@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum File {
@@ -26,7 +26,7 @@ pub const ALL_FILES: [File; NUM_FILES] = [
 ];
 
 impl File {
-    pub fn from_index(index: usize) -> Self {
+    pub const fn from_index(index: usize) -> Self {
         match index {
             0 => File::A,
             1 => File::B,
@@ -70,7 +70,7 @@ impl File {
         }
     }
 
-    pub fn to_index(&self) -> usize {
+    pub const fn to_index(&self) -> usize {
         *self as usize
     }
 }
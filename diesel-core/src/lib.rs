@@ -1,4 +1,9 @@
-extern crate core;
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod piece;
 pub mod color;
@@ -7,6 +12,14 @@ pub mod file;
 pub mod square;
 pub mod bitboard;
 pub mod chess_move;
+pub mod move_list;
 pub mod board;
 pub mod castling;
-pub mod magic;
\ No newline at end of file
+pub mod magic;
+pub mod direction;
+pub mod tables;
+pub mod zobrist;
+pub mod eval;
+
+#[cfg(feature = "serde")]
+mod serialization;
@@ -1,16 +1,41 @@
 use crate::bitboard::{BitBoard};
 
+/// `true` when sliding attack tables are indexed with `_pext_u64` rather than the magic multiply.
+///
+/// This requires both the `bmi2` feature and a target with BMI2 enabled at compile time
+/// (e.g. `RUSTFLAGS="-C target-cpu=native"`); otherwise the magic index is used as a fallback.
+pub const PEXT_ENABLED: bool = cfg!(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2"));
+
 #[derive(Clone, Copy, Debug)]
 pub struct Magic {
     pub mask: BitBoard,
     pub magic: u64,
     pub shift: u8,
+    pub offset: usize,
 }
 
 impl Magic {
-    pub fn get_magic_index(self: &Magic, blocker_bitboard: BitBoard) -> usize {
-        let blockers = blocker_bitboard & self.mask;
-        let hash = blockers.0.wrapping_mul(self.magic);
+    pub const fn get_magic_index(self: &Magic, blocker_bitboard: BitBoard) -> usize {
+        let blockers = blocker_bitboard.0 & self.mask.0;
+        let hash = blockers.wrapping_mul(self.magic);
         (hash >> self.shift) as usize
     }
-}
\ No newline at end of file
+
+    #[cfg(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2"))]
+    pub fn get_pext_index(self: &Magic, blocker_bitboard: BitBoard) -> usize {
+        // SAFETY: only compiled when the target is known to support BMI2.
+        unsafe { core::arch::x86_64::_pext_u64(blocker_bitboard.0, self.mask.0) as usize }
+    }
+
+    /// Returns the index of `blocker_bitboard` within this square's slice of the attack table.
+    #[cfg(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2"))]
+    pub fn get_index(self: &Magic, blocker_bitboard: BitBoard) -> usize {
+        self.get_pext_index(blocker_bitboard)
+    }
+
+    /// Returns the index of `blocker_bitboard` within this square's slice of the attack table.
+    #[cfg(not(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2")))]
+    pub fn get_index(self: &Magic, blocker_bitboard: BitBoard) -> usize {
+        self.get_magic_index(blocker_bitboard)
+    }
+}
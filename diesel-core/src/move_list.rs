@@ -0,0 +1,60 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::chess_move::ChessMove;
+
+/// More than the number of legal moves in any reachable position (218).
+pub const MAX_MOVES: usize = 256;
+
+/// A fixed capacity list of moves, so move generation never allocates.
+#[derive(Clone, Copy, Debug)]
+pub struct MoveList {
+    moves: [ChessMove; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self {
+            moves: [ChessMove::default(); MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, chess_move: ChessMove) {
+        self.moves[self.len] = chess_move;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [ChessMove];
+
+    fn deref(&self) -> &Self::Target {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a ChessMove;
+    type IntoIter = core::slice::Iter<'a, ChessMove>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
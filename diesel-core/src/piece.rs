@@ -1,4 +1,7 @@
-use std::fmt;
+use core::fmt;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
 use crate::color::Color;
 
 #[derive(PartialEq, Eq, Ord, PartialOrd, Copy, Clone, Debug, Hash)]
@@ -23,33 +26,53 @@ pub const ALL_PIECES: [Piece; NUM_PIECES] = [
 ];
 
 impl Piece {
-    pub fn to_string(&self, color: Color) -> String {
-        let piece = format!("{}", self);
+    /// Returns the FEN letter for this piece, uppercase for White.
+    pub fn to_char(&self, color: Color) -> char {
+        let piece = match self {
+            Piece::Pawn => 'p',
+            Piece::Knight => 'n',
+            Piece::Bishop => 'b',
+            Piece::Rook => 'r',
+            Piece::Queen => 'q',
+            Piece::King => 'k',
+        };
         if color == Color::White {
-            piece.to_uppercase()
+            piece.to_ascii_uppercase()
         } else {
             piece
         }
     }
-    pub fn to_fancy_string(&self, color: Color) -> String {
+
+    /// Returns the Unicode chess figurine for this piece.
+    pub fn to_fancy_char(&self, color: Color) -> char {
         match color {
             Color::White => match self {
-                Piece::Pawn => "♙",
-                Piece::Knight => "♘",
-                Piece::Bishop => "♗",
-                Piece::Rook => "♖",
-                Piece::Queen => "♕",
-                Piece::King => "♔",
+                Piece::Pawn => '♙',
+                Piece::Knight => '♘',
+                Piece::Bishop => '♗',
+                Piece::Rook => '♖',
+                Piece::Queen => '♕',
+                Piece::King => '♔',
             },
             Color::Black => match self {
-                Piece::Pawn => "♟",
-                Piece::Knight => "♞",
-                Piece::Bishop => "♝",
-                Piece::Rook => "♜",
-                Piece::Queen => "♛",
-                Piece::King => "♚",
+                Piece::Pawn => '♟',
+                Piece::Knight => '♞',
+                Piece::Bishop => '♝',
+                Piece::Rook => '♜',
+                Piece::Queen => '♛',
+                Piece::King => '♚',
             },
-        }.to_string()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn to_string(&self, color: Color) -> String {
+        self.to_char(color).into()
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn to_fancy_string(&self, color: Color) -> String {
+        self.to_fancy_char(color).into()
     }
 
     pub fn from_char(c: char) -> Result<Self, ()> {
@@ -71,13 +94,6 @@ impl Piece {
 
 impl fmt::Display for Piece {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Piece::Pawn => write!(f, "p"),
-            Piece::Knight => write!(f, "n"),
-            Piece::Bishop => write!(f, "b"),
-            Piece::Rook => write!(f, "r"),
-            Piece::Queen => write!(f, "q"),
-            Piece::King => write!(f, "k"),
-        }
+        write!(f, "{}", self.to_char(Color::Black))
     }
 }
\ No newline at end of file
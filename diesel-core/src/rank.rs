@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Rank {
@@ -26,7 +26,7 @@ pub const ALL_RANKS: [Rank; NUM_RANKS] = [
 ];
 
 impl Rank {
-    pub fn from_index(index: usize) -> Self {
+    pub const fn from_index(index: usize) -> Self {
         match index {
             0 => Rank::First,
             1 => Rank::Second,
@@ -71,7 +71,7 @@ impl Rank {
         }
     }
 
-    pub fn to_index(&self) -> usize {
+    pub const fn to_index(&self) -> usize {
         *self as usize
     }
 }
@@ -0,0 +1,211 @@
+// Serde support, enabled by the `serde` feature.
+//
+// Human-readable formats (JSON, TOML, ...) get the usual chess notation: squares as "e4", moves as UCI
+// strings and boards as FEN. Binary formats get compact integers, and boards use `Board::to_bytes`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::bitboard::BitBoard;
+use crate::board::{Board, FULL_ENCODED_LEN, MAX_ENCODED_LEN};
+use crate::castling::CastlingRights;
+use crate::chess_move::ChessMove;
+use crate::color::{ALL_COLORS, Color};
+use crate::file::{ALL_FILES, File};
+use crate::piece::{ALL_PIECES, Piece};
+use crate::rank::{ALL_RANKS, Rank};
+use crate::square::{NUM_SQUARES, Square};
+
+fn serialize_readable_or<S: Serializer, C: Serialize>(serializer: S, readable: impl Display, compact: C) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.collect_str(&readable)
+    } else {
+        compact.serialize(serializer)
+    }
+}
+
+fn deserialize_readable_or<'de, D, T, C>(deserializer: D, parse: impl FnOnce(&str) -> Option<T>, from_compact: impl FnOnce(C) -> Option<T>, expected: &str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    C: Deserialize<'de> + Display,
+{
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        parse(&s).ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&s), &expected))
+    } else {
+        let compact = C::deserialize(deserializer)?;
+        let description = compact.to_string();
+        from_compact(compact).ok_or_else(|| de::Error::invalid_value(de::Unexpected::Other(&description), &expected))
+    }
+}
+
+fn parse<T: FromStr>(s: &str) -> Option<T> {
+    s.parse::<T>().ok()
+}
+
+impl Serialize for Square {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_readable_or(serializer, self, self.to_int())
+    }
+}
+
+impl<'de> Deserialize<'de> for Square {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let from_compact = |index: u8| ((index as usize) < NUM_SQUARES).then(|| Square::new(index));
+        deserialize_readable_or(deserializer, parse, from_compact, "a square such as \"e4\"")
+    }
+}
+
+impl Serialize for Piece {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_readable_or(serializer, self, self.to_index() as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Piece {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let parse = |s: &str| match s.chars().collect::<Vec<char>>()[..] {
+            [c] => Piece::from_char(c).ok(),
+            _ => None,
+        };
+        let from_compact = |index: u8| ALL_PIECES.get(index as usize).copied();
+        deserialize_readable_or(deserializer, parse, from_compact, "one of \"p\", \"n\", \"b\", \"r\", \"q\" or \"k\"")
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let readable = match self {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+        serialize_readable_or(serializer, readable, self.to_index() as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let from_compact = |index: u8| ALL_COLORS.get(index as usize).copied();
+        deserialize_readable_or(deserializer, parse, from_compact, "\"w\" or \"b\"")
+    }
+}
+
+impl Serialize for File {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let readable = (b'a' + self.to_index() as u8) as char;
+        serialize_readable_or(serializer, readable, self.to_index() as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for File {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let from_compact = |index: u8| ALL_FILES.get(index as usize).copied();
+        deserialize_readable_or(deserializer, parse, from_compact, "a file from \"a\" to \"h\"")
+    }
+}
+
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let readable = (b'1' + self.to_index() as u8) as char;
+        serialize_readable_or(serializer, readable, self.to_index() as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let from_compact = |index: u8| ALL_RANKS.get(index as usize).copied();
+        deserialize_readable_or(deserializer, parse, from_compact, "a rank from \"1\" to \"8\"")
+    }
+}
+
+// A plain integer in every format, since there is no more readable notation for a set of squares
+impl Serialize for BitBoard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BitBoard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(BitBoard)
+    }
+}
+
+impl Serialize for CastlingRights {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_readable_or(serializer, self, self.to_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for CastlingRights {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let from_compact = |bits: u8| (bits < 16).then(|| CastlingRights::from_bytes(bits));
+        deserialize_readable_or(deserializer, parse, from_compact, "castling rights such as \"KQkq\" or \"-\"")
+    }
+}
+
+impl Serialize for ChessMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_readable_or(serializer, self, self.to_u16())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChessMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_readable_or(deserializer, parse, ChessMove::from_u16, "a UCI move such as \"e2e4\"")
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_fen())
+        } else {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+}
+
+struct BoardBytesVisitor;
+
+impl<'de> Visitor<'de> for BoardBytesVisitor {
+    type Value = Board;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "a board encoded with Board::to_bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Board::from_bytes(bytes).map_err(|error| E::custom(format!("invalid board encoding: {:?}", error)))
+    }
+
+    // Formats without a native byte type hand the bytes over as a sequence
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // The size hint comes from the input, so it can't be trusted with the allocation
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX_ENCODED_LEN));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            if bytes.len() == FULL_ENCODED_LEN {
+                return Err(de::Error::invalid_length(bytes.len() + 1, &self));
+            }
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let fen = String::deserialize(deserializer)?;
+            Board::parse_fen(&fen).map_err(|error| de::Error::custom(format!("invalid FEN {:?}: {:?}", fen, error)))
+        } else {
+            deserializer.deserialize_bytes(BoardBytesVisitor)
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use std::fmt;
-use std::fmt::Display;
-use std::str::FromStr;
+use core::fmt;
+use core::fmt::Display;
+use core::str::FromStr;
+use crate::color::Color;
+use crate::direction::Direction;
 use crate::file::{File, NUM_FILES};
 use crate::rank::{NUM_RANKS, Rank};
 
@@ -9,6 +11,13 @@ pub struct Square(u8);
 
 pub const NUM_SQUARES: usize = 64;
 
+/// The color of a square on the board, a1 being dark.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash)]
+pub enum SquareColor {
+    Light,
+    Dark,
+}
+
 impl Default for Square {
     fn default() -> Self {
         Square(0)
@@ -16,69 +25,133 @@ impl Default for Square {
 }
 
 impl Square {
-    pub fn new(square: u8) -> Self {
+    pub const fn new(square: u8) -> Self {
         Square(square & 63)
     }
 
-    pub fn create_square(rank: Rank, file: File) -> Self {
+    pub const fn create_square(rank: Rank, file: File) -> Self {
         Square::new((rank.to_index() as u8) << 3 ^ (file.to_index() as u8))
     }
 
-    pub fn get_rank(&self) -> Rank {
+    pub const fn get_rank(&self) -> Rank {
         Rank::from_index((self.0 >> 3) as usize)
     }
 
-    pub fn get_file(&self) -> File {
+    pub const fn get_file(&self) -> File {
         File::from_index((self.0 & 7) as usize)
     }
 
-    pub fn up(&self) -> Option<Square> {
-        if self.get_rank() != Rank::Eighth {
-            Some(Square::new(self.to_int() + 8))
+    pub const fn up(&self) -> Option<Square> {
+        self.shift(Direction::North)
+    }
+
+    pub const fn down(&self) -> Option<Square> {
+        self.shift(Direction::South)
+    }
+
+    /// Returns the square `file_offset` files and `rank_offset` ranks away, or `None` if it is off the board.
+    pub const fn offset(&self, file_offset: i32, rank_offset: i32) -> Option<Square> {
+        let new_file = (self.0 & 7) as i32 + file_offset;
+        let new_rank = (self.0 >> 3) as i32 + rank_offset;
+
+        if new_file >= 0 && new_file < NUM_FILES as i32 && new_rank >= 0 && new_rank < NUM_RANKS as i32 {
+            Some(Square::new((new_rank * 8 + new_file) as u8))
         } else {
             None
         }
     }
 
-    pub fn down(&self) -> Option<Square> {
-        if self.get_rank() != Rank::First {
-            Some(Square::new(self.to_int() - 8))
-        } else {
-            None
+    /// Returns the square one step away in `direction`, or `None` if it is off the board.
+    pub const fn shift(&self, direction: Direction) -> Option<Square> {
+        let (file_offset, rank_offset) = direction.get_offset();
+        self.offset(file_offset, rank_offset)
+    }
+
+    /// Returns the rank from `color`'s point of view, so a black pawn on the seventh rank is on its second.
+    pub const fn relative_rank(&self, color: Color) -> Rank {
+        match color {
+            Color::White => self.get_rank(),
+            Color::Black => Rank::from_index(NUM_RANKS - 1 - (self.0 >> 3) as usize),
         }
     }
 
-    pub fn offset(&self, file_offset: i32, rank_offset: i32) -> Option<Square> {
-        let new_file = (self.get_file() as i32 + file_offset) as usize;
-        let new_rank = (self.get_rank() as i32 + rank_offset) as usize;
+    /// Mirrors the square across the horizontal axis, so a1 becomes a8.
+    pub const fn flip(&self) -> Square {
+        Square(self.0 ^ 56)
+    }
 
-        if new_file < NUM_FILES && new_rank < NUM_RANKS {
-            Some(Square::create_square(Rank::from_index(new_rank), File::from_index(new_file)))
+    pub const fn color(&self) -> SquareColor {
+        if ((self.0 >> 3) + (self.0 & 7)) & 1 == 0 {
+            SquareColor::Dark
         } else {
-            None
+            SquareColor::Light
         }
     }
 
-    pub fn to_int(&self) -> u8 {
+    pub const fn is_light(&self) -> bool {
+        matches!(self.color(), SquareColor::Light)
+    }
+
+    /// Iterates over the squares reached by repeatedly stepping in `direction`, excluding this square.
+    pub const fn iter_ray(&self, direction: Direction) -> RayIter {
+        RayIter { square: *self, direction }
+    }
+
+    pub const fn to_int(&self) -> u8 {
         self.0
     }
 
-    pub fn to_index(&self) -> usize {
+    pub const fn to_index(&self) -> usize {
         self.0 as usize
     }
 }
 
+/// Returns the Chebyshev (king move) distance between `a` and `b`.
+pub const fn distance(a: Square, b: Square) -> u8 {
+    let file_distance = ((a.0 & 7) as i8 - (b.0 & 7) as i8).unsigned_abs();
+    let rank_distance = ((a.0 >> 3) as i8 - (b.0 >> 3) as i8).unsigned_abs();
+    if file_distance > rank_distance { file_distance } else { rank_distance }
+}
+
+/// Returns `true` if `a`, `b` and `c` all lie on a single rank, file or diagonal.
+pub const fn aligned(a: Square, b: Square, c: Square) -> bool {
+    const fn is_line(file_offset: i32, rank_offset: i32) -> bool {
+        file_offset == 0 || rank_offset == 0 || file_offset == rank_offset || file_offset == -rank_offset
+    }
+
+    let (ab_file, ab_rank) = ((b.0 & 7) as i32 - (a.0 & 7) as i32, (b.0 >> 3) as i32 - (a.0 >> 3) as i32);
+    let (ac_file, ac_rank) = ((c.0 & 7) as i32 - (a.0 & 7) as i32, (c.0 >> 3) as i32 - (a.0 >> 3) as i32);
+    is_line(ab_file, ab_rank) && is_line(ac_file, ac_rank) && ab_file * ac_rank == ab_rank * ac_file
+}
+
+/// Iterator over the squares along a ray, see [`Square::iter_ray`].
+#[derive(Clone, Debug)]
+pub struct RayIter {
+    square: Square,
+    direction: Direction,
+}
+
+impl Iterator for RayIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.square.shift(self.direction)?;
+        self.square = next;
+        Some(next)
+    }
+}
+
 impl FromStr for Square {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 2 {
+        let mut chars = s.chars();
+        let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
             return Err(());
-        }
-        let chars: Vec<char> = s.chars().collect::<Vec<char>>();
+        };
 
-        let file = File::from_char(chars[0]).unwrap();
-        let rank = Rank::from_char(chars[1]).unwrap();
+        let file = File::from_char(file)?;
+        let rank = Rank::from_char(rank)?;
         Ok(Square::create_square(rank, file))
     }
 }
@@ -0,0 +1,334 @@
+// Compile-time generators for the lookup tables. These are `const fn`, so they are limited to
+// `while` loops and plain integer arithmetic on ranks and files.
+
+use crate::bitboard::{BitBoard, EMPTY_BITBOARD};
+use crate::color::NUM_COLORS;
+use crate::direction::{Direction, ALL_RAY_DIRECTIONS, NUM_RAY_DIRECTIONS};
+use crate::file::NUM_FILES;
+use crate::magic::{Magic, PEXT_ENABLED};
+use crate::rank::NUM_RANKS;
+use crate::square::NUM_SQUARES;
+
+const KING_OFFSETS: [(i32, i32); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+
+pub(super) const ROOK_DIRECTIONS: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+pub(super) const BISHOP_DIRECTIONS: [Direction; 4] =
+    [Direction::NorthEast, Direction::SouthEast, Direction::SouthWest, Direction::NorthWest];
+
+const fn rank_of(square: usize) -> i32 {
+    (square >> 3) as i32
+}
+
+const fn file_of(square: usize) -> i32 {
+    (square & 7) as i32
+}
+
+// Returns the bit for the square `(file, rank)`, or 0 if it is off the board
+const fn bit_at(file: i32, rank: i32) -> u64 {
+    if file < 0 || file >= NUM_FILES as i32 || rank < 0 || rank >= NUM_RANKS as i32 {
+        0
+    } else {
+        1u64 << (rank * 8 + file)
+    }
+}
+
+const fn bit_offset(square: usize, file_offset: i32, rank_offset: i32) -> u64 {
+    bit_at(file_of(square) + file_offset, rank_of(square) + rank_offset)
+}
+
+pub(super) const fn generate_rank_masks() -> [BitBoard; NUM_RANKS] {
+    let mut masks = [EMPTY_BITBOARD; NUM_RANKS];
+    let mut rank = 0;
+    while rank < NUM_RANKS {
+        masks[rank] = BitBoard(0xFF << (rank * 8));
+        rank += 1;
+    }
+    masks
+}
+
+pub(super) const fn generate_file_masks() -> [BitBoard; NUM_FILES] {
+    let mut masks = [EMPTY_BITBOARD; NUM_FILES];
+    let mut file = 0;
+    while file < NUM_FILES {
+        masks[file] = BitBoard(0x0101_0101_0101_0101 << file);
+        file += 1;
+    }
+    masks
+}
+
+pub(super) const fn generate_adjacent_file_masks() -> [BitBoard; NUM_FILES] {
+    let file_masks = generate_file_masks();
+    let mut masks = [EMPTY_BITBOARD; NUM_FILES];
+    let mut file = 0;
+    while file < NUM_FILES {
+        if file > 0 {
+            masks[file].0 |= file_masks[file - 1].0;
+        }
+        if file + 1 < NUM_FILES {
+            masks[file].0 |= file_masks[file + 1].0;
+        }
+        file += 1;
+    }
+    masks
+}
+
+pub(super) const fn generate_edge_mask() -> BitBoard {
+    let rank_masks = generate_rank_masks();
+    let file_masks = generate_file_masks();
+    BitBoard(rank_masks[0].0 | rank_masks[NUM_RANKS - 1].0 | file_masks[0].0 | file_masks[NUM_FILES - 1].0)
+}
+
+const fn generate_leaper_moves(offsets: &[(i32, i32)]) -> [BitBoard; NUM_SQUARES] {
+    let mut moves = [EMPTY_BITBOARD; NUM_SQUARES];
+    let mut square = 0;
+    while square < NUM_SQUARES {
+        let mut i = 0;
+        while i < offsets.len() {
+            moves[square].0 |= bit_offset(square, offsets[i].0, offsets[i].1);
+            i += 1;
+        }
+        square += 1;
+    }
+    moves
+}
+
+pub(super) const fn generate_king_moves() -> [BitBoard; NUM_SQUARES] {
+    generate_leaper_moves(&KING_OFFSETS)
+}
+
+pub(super) const fn generate_knight_moves() -> [BitBoard; NUM_SQUARES] {
+    generate_leaper_moves(&KNIGHT_OFFSETS)
+}
+
+// Indexed by color, with White moving up the board
+const fn pawn_direction(color: usize) -> i32 {
+    if color == 0 { 1 } else { -1 }
+}
+
+pub(super) const fn generate_pawn_attacks() -> [[BitBoard; NUM_SQUARES]; NUM_COLORS] {
+    let mut attacks = [[EMPTY_BITBOARD; NUM_SQUARES]; NUM_COLORS];
+    let mut color = 0;
+    while color < NUM_COLORS {
+        let forward = pawn_direction(color);
+        let mut square = 0;
+        while square < NUM_SQUARES {
+            attacks[color][square] = BitBoard(bit_offset(square, -1, forward) | bit_offset(square, 1, forward));
+            square += 1;
+        }
+        color += 1;
+    }
+    attacks
+}
+
+/// Single pushes, plus double pushes from the starting rank, ignoring blockers.
+pub(super) const fn generate_pawn_moves() -> [[BitBoard; NUM_SQUARES]; NUM_COLORS] {
+    let mut moves = [[EMPTY_BITBOARD; NUM_SQUARES]; NUM_COLORS];
+    let mut color = 0;
+    while color < NUM_COLORS {
+        let forward = pawn_direction(color);
+        let start_rank = if color == 0 { 1 } else { NUM_RANKS as i32 - 2 };
+        let mut square = 0;
+        while square < NUM_SQUARES {
+            moves[color][square].0 = bit_offset(square, 0, forward);
+            if rank_of(square) == start_rank {
+                moves[color][square].0 |= bit_offset(square, 0, 2 * forward);
+            }
+            square += 1;
+        }
+        color += 1;
+    }
+    moves
+}
+
+// Slides from `square` in `direction` until it leaves the board or passes through a blocker
+const fn slide(square: usize, direction: Direction, blockers: u64) -> u64 {
+    let (file_offset, rank_offset) = direction.get_offset();
+    let mut attacks = 0;
+    let mut distance = 1;
+    loop {
+        let bit = bit_offset(square, file_offset * distance, rank_offset * distance);
+        attacks |= bit;
+        if bit == 0 || bit & blockers != 0 {
+            return attacks;
+        }
+        distance += 1;
+    }
+}
+
+const fn generate_slider_rays(directions: &[Direction]) -> [BitBoard; NUM_SQUARES] {
+    let mut rays = [EMPTY_BITBOARD; NUM_SQUARES];
+    let mut square = 0;
+    while square < NUM_SQUARES {
+        let mut i = 0;
+        while i < directions.len() {
+            rays[square].0 |= slide(square, directions[i], 0);
+            i += 1;
+        }
+        square += 1;
+    }
+    rays
+}
+
+pub(super) const fn generate_rook_rays() -> [BitBoard; NUM_SQUARES] {
+    generate_slider_rays(&ROOK_DIRECTIONS)
+}
+
+pub(super) const fn generate_bishop_rays() -> [BitBoard; NUM_SQUARES] {
+    generate_slider_rays(&BISHOP_DIRECTIONS)
+}
+
+pub(super) const fn generate_rays() -> [[BitBoard; NUM_SQUARES]; NUM_RAY_DIRECTIONS] {
+    let mut rays = [[EMPTY_BITBOARD; NUM_SQUARES]; NUM_RAY_DIRECTIONS];
+    let mut direction = 0;
+    while direction < NUM_RAY_DIRECTIONS {
+        let mut square = 0;
+        while square < NUM_SQUARES {
+            rays[direction][square].0 = slide(square, ALL_RAY_DIRECTIONS[direction], 0);
+            square += 1;
+        }
+        direction += 1;
+    }
+    rays
+}
+
+// Returns the direction from `src` that reaches `dst`, if they share a rank, file or diagonal
+const fn direction_between(rays: &[[BitBoard; NUM_SQUARES]; NUM_RAY_DIRECTIONS], src: usize, dst: usize) -> Option<Direction> {
+    let mut direction = 0;
+    while direction < NUM_RAY_DIRECTIONS {
+        if rays[direction][src].0 & (1u64 << dst) != 0 {
+            return Some(ALL_RAY_DIRECTIONS[direction]);
+        }
+        direction += 1;
+    }
+    None
+}
+
+pub(super) const fn generate_between() -> [[BitBoard; NUM_SQUARES]; NUM_SQUARES] {
+    let rays = generate_rays();
+    let mut between = [[EMPTY_BITBOARD; NUM_SQUARES]; NUM_SQUARES];
+    let mut src = 0;
+    while src < NUM_SQUARES {
+        let mut dst = 0;
+        while dst < NUM_SQUARES {
+            if let Some(direction) = direction_between(&rays, src, dst) {
+                between[src][dst].0 = rays[direction.to_index()][src].0 & rays[direction.opposite().to_index()][dst].0;
+            }
+            dst += 1;
+        }
+        src += 1;
+    }
+    between
+}
+
+pub(super) const fn generate_line() -> [[BitBoard; NUM_SQUARES]; NUM_SQUARES] {
+    let rays = generate_rays();
+    let mut line = [[EMPTY_BITBOARD; NUM_SQUARES]; NUM_SQUARES];
+    let mut src = 0;
+    while src < NUM_SQUARES {
+        let mut dst = 0;
+        while dst < NUM_SQUARES {
+            if let Some(direction) = direction_between(&rays, src, dst) {
+                line[src][dst].0 = rays[direction.to_index()][src].0
+                    | rays[direction.opposite().to_index()][src].0
+                    | (1u64 << src);
+            }
+            dst += 1;
+        }
+        src += 1;
+    }
+    line
+}
+
+const fn generate_distance_table(manhattan: bool) -> [[u8; NUM_SQUARES]; NUM_SQUARES] {
+    let mut distances = [[0; NUM_SQUARES]; NUM_SQUARES];
+    let mut src = 0;
+    while src < NUM_SQUARES {
+        let mut dst = 0;
+        while dst < NUM_SQUARES {
+            let rank_distance = (rank_of(src) - rank_of(dst)).unsigned_abs() as u8;
+            let file_distance = (file_of(src) - file_of(dst)).unsigned_abs() as u8;
+            distances[src][dst] = if manhattan {
+                rank_distance + file_distance
+            } else if rank_distance > file_distance {
+                rank_distance
+            } else {
+                file_distance
+            };
+            dst += 1;
+        }
+        src += 1;
+    }
+    distances
+}
+
+pub(super) const fn generate_distances() -> [[u8; NUM_SQUARES]; NUM_SQUARES] {
+    generate_distance_table(false)
+}
+
+pub(super) const fn generate_manhattan_distances() -> [[u8; NUM_SQUARES]; NUM_SQUARES] {
+    generate_distance_table(true)
+}
+
+// Software PEXT, used to lay the tables out for the hardware instruction at compile time
+const fn pext(value: u64, mut mask: u64) -> u64 {
+    let mut result = 0;
+    let mut bit = 1;
+    while mask != 0 {
+        if value & mask & mask.wrapping_neg() != 0 {
+            result |= bit;
+        }
+        mask &= mask - 1;
+        bit <<= 1;
+    }
+    result
+}
+
+// Classical ray attacks: cut each ray at its first blocker using the ray from that blocker onwards
+const fn ray_attacks(rays: &[[BitBoard; NUM_SQUARES]; NUM_RAY_DIRECTIONS], square: usize, direction: Direction, blockers: u64) -> u64 {
+    let ray = rays[direction.to_index()][square].0;
+    let blocked = ray & blockers;
+    if blocked == 0 {
+        return ray;
+    }
+    let (file_offset, rank_offset) = direction.get_offset();
+    let blocker = if rank_offset > 0 || (rank_offset == 0 && file_offset > 0) {
+        blocked.trailing_zeros() as usize
+    } else {
+        63 - blocked.leading_zeros() as usize
+    };
+    ray ^ rays[direction.to_index()][blocker].0
+}
+
+/// Fills the attack table for the given magics, indexed the same way as `Magic::get_index`.
+pub(super) const fn generate_sliding_attacks<const N: usize>(magics: &[Magic; NUM_SQUARES], directions: &[Direction; 4]) -> [BitBoard; N] {
+    let rays = generate_rays();
+    let mut table = [EMPTY_BITBOARD; N];
+    let mut square = 0;
+    while square < NUM_SQUARES {
+        let magic = &magics[square];
+
+        // Enumerate every subset of the mask with the Carry-Rippler trick
+        let mut blockers: u64 = 0;
+        loop {
+            let attacks = ray_attacks(&rays, square, directions[0], blockers)
+                | ray_attacks(&rays, square, directions[1], blockers)
+                | ray_attacks(&rays, square, directions[2], blockers)
+                | ray_attacks(&rays, square, directions[3], blockers);
+
+            let index = if PEXT_ENABLED {
+                pext(blockers, magic.mask.0) as usize
+            } else {
+                magic.get_magic_index(BitBoard(blockers))
+            };
+            table[magic.offset + index] = BitBoard(attacks);
+
+            blockers = blockers.wrapping_sub(magic.mask.0) & magic.mask.0;
+            if blockers == 0 {
+                break;
+            }
+        }
+        square += 1;
+    }
+    table
+}
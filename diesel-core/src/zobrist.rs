@@ -0,0 +1,25 @@
+use crate::castling::{CastlingRights, NUM_CASTLING_RIGHTS};
+use crate::color::Color;
+use crate::file::File;
+use crate::piece::Piece;
+use crate::square::Square;
+use crate::tables::{ZOBRIST_CASTLING, ZOBRIST_EN_PASSANT, ZOBRIST_PIECES, ZOBRIST_SIDE};
+
+pub fn get_piece_key(piece: Piece, color: Color, square: Square) -> u64 {
+    ZOBRIST_PIECES[color.to_index()][piece.to_index()][square.to_index()]
+}
+
+/// Returns the combined key of every right in `castling_rights`.
+pub fn get_castling_key(castling_rights: CastlingRights) -> u64 {
+    (0..NUM_CASTLING_RIGHTS)
+        .filter(|i| castling_rights.has(1 << i))
+        .fold(0, |key, i| key ^ ZOBRIST_CASTLING[i])
+}
+
+pub fn get_en_passant_key(file: File) -> u64 {
+    ZOBRIST_EN_PASSANT[file.to_index()]
+}
+
+pub fn get_side_key() -> u64 {
+    ZOBRIST_SIDE
+}
@@ -1,4 +1,6 @@
-use chess_core::bitboard::{BitBoard, EMPTY_BITBOARD};
+use diesel_core::bitboard::{BitBoard, EMPTY_BITBOARD};
+use diesel_core::direction::Direction;
+use diesel_core::square::*;
 
 #[cfg(test)]
 mod tests {
@@ -78,4 +80,83 @@ mod tests {
         assert_eq!(EMPTY_BITBOARD.0, 0);
     }
 
+    fn bitboard_of(squares: &[Square]) -> BitBoard {
+        squares.iter().fold(EMPTY_BITBOARD, |bitboard, square| bitboard | BitBoard::from_square(*square))
+    }
+
+    #[test]
+    fn test_iterator() {
+        let board = bitboard_of(&[H8, A1, E4]);
+
+        assert_eq!(board.len(), 3);
+        assert_eq!(board.collect::<Vec<Square>>(), vec![A1, E4, H8]);
+        assert_eq!(EMPTY_BITBOARD.count(), 0);
+    }
+
+    #[test]
+    fn test_pop_lsb() {
+        let mut board = bitboard_of(&[C3, F6]);
+
+        assert_eq!(board.pop_lsb(), Some(C3));
+        assert_eq!(board.pop_lsb(), Some(F6));
+        assert_eq!(board.pop_lsb(), None);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let board = bitboard_of(&[B2, G7]);
+
+        assert!(board.contains(B2));
+        assert!(!board.contains(B3));
+    }
+
+    #[test]
+    fn test_shift_masks_wraparound() {
+        assert_eq!(BitBoard::from_square(H4).shift(Direction::East), EMPTY_BITBOARD);
+        assert_eq!(BitBoard::from_square(A4).shift(Direction::West), EMPTY_BITBOARD);
+        assert_eq!(BitBoard::from_square(A4).shift(Direction::NorthWest), EMPTY_BITBOARD);
+        assert_eq!(BitBoard::from_square(H8).shift(Direction::North), EMPTY_BITBOARD);
+        assert_eq!(BitBoard::from_square(E4).shift(Direction::SouthWest), BitBoard::from_square(D3));
+        assert_eq!(BitBoard::from_square(E4).shift(Direction::NorthEast), BitBoard::from_square(F5));
+        assert_eq!(BitBoard::from_square(B1).shift(Direction::NorthNorthEast), BitBoard::from_square(C3));
+        assert_eq!(BitBoard::from_square(G1).shift(Direction::EastNorthEast), EMPTY_BITBOARD);
+        assert_eq!(BitBoard::from_square(B8).shift(Direction::WestSouthWest), EMPTY_BITBOARD);
+    }
+
+    #[test]
+    fn test_fills() {
+        let board = BitBoard::from_square(C4);
+
+        assert_eq!(board.north_fill(), bitboard_of(&[C4, C5, C6, C7, C8]));
+        assert_eq!(board.south_fill(), bitboard_of(&[C1, C2, C3, C4]));
+        assert_eq!(board.file_fill(), bitboard_of(&[C1, C2, C3, C4, C5, C6, C7, C8]));
+    }
+
+    #[test]
+    fn test_sliding_attacks() {
+        let empty = !bitboard_of(&[A1, F4]);
+
+        assert_eq!(BitBoard::from_square(A1).occluded_fill(Direction::NorthEast, empty), bitboard_of(&[A1, B2, C3, D4, E5, F6, G7, H8]));
+        assert_eq!(BitBoard::from_square(A4).sliding_attacks(Direction::East, empty), bitboard_of(&[B4, C4, D4, E4, F4]));
+        assert_eq!(BitBoard::from_square(H4).sliding_attacks(Direction::East, empty), EMPTY_BITBOARD);
+    }
+
+    #[test]
+    fn test_flip_and_mirror() {
+        let board = bitboard_of(&[A1, B2, H3]);
+
+        assert_eq!(board.flip_vertical(), bitboard_of(&[A8, B7, H6]));
+        assert_eq!(board.mirror_horizontal(), bitboard_of(&[H1, G2, A3]));
+    }
+
+    #[test]
+    fn test_shl_shr_sub() {
+        let board = bitboard_of(&[A1, B2]);
+
+        assert_eq!(board << 8, bitboard_of(&[A2, B3]));
+        assert_eq!(board >> 1, BitBoard::from_square(A2));
+        assert_eq!(board << 64, EMPTY_BITBOARD);
+        assert_eq!(board - BitBoard::from_square(A1), BitBoard::from_square(B2));
+    }
 }
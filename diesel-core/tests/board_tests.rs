@@ -0,0 +1,147 @@
+use diesel_core::bitboard::BitBoard;
+use diesel_core::board::builder::BoardBuilder;
+use diesel_core::board::{Board, BoardParseError, InvariantViolation};
+use diesel_core::castling::WHITE_QUEEN_SIDE;
+use diesel_core::color::Color;
+use diesel_core::piece::Piece;
+use diesel_core::square::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_piece_at() {
+        let board = Board::default();
+
+        assert_eq!(board.get_piece_at(E1), Some((Piece::King, Color::White)));
+        assert_eq!(board.get_piece_at(D8), Some((Piece::Queen, Color::Black)));
+        assert_eq!(board.get_piece_at(B7), Some((Piece::Pawn, Color::Black)));
+        assert_eq!(board.get_piece_at(E4), None);
+        assert!(board.is_mailbox_consistent());
+    }
+
+    #[test]
+    fn test_set_piece_at_replaces() {
+        let mut board = Board::default();
+
+        board.set_piece_at(BitBoard::from_square(E2), Piece::Knight, Color::Black);
+
+        assert_eq!(board.get_piece_at(E2), Some((Piece::Knight, Color::Black)));
+        assert!(!board.get_pawn_bitboard(Color::White).contains(E2));
+        assert!(!board.get_white_pieces_bitboard().contains(E2));
+        assert!(board.is_mailbox_consistent());
+    }
+
+    #[test]
+    fn test_clear_piece_at() {
+        let mut board = Board::default();
+
+        board.clear_piece_at(BitBoard::from_square(A1) | BitBoard::from_square(H8));
+
+        assert_eq!(board.get_piece_at(A1), None);
+        assert_eq!(board.get_piece_at(H8), None);
+        assert!(!board.get_occupancy_bitboard().contains(A1));
+        assert_eq!(board.get_hash(), board.compute_hash());
+        assert!(board.is_mailbox_consistent());
+    }
+
+    #[test]
+    fn test_validate_invariants() {
+        assert_eq!(Board::default().validate_invariants(), Ok(()));
+        assert_eq!(Board::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap().validate_invariants(), Ok(()));
+        assert_eq!(Board::parse_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap().validate_invariants(), Ok(()));
+    }
+
+    fn builder(pieces: &[(Square, Piece, Color)]) -> BoardBuilder {
+        let mut squares = [None; NUM_SQUARES];
+        for &(square, piece, color) in pieces {
+            squares[square.to_index()] = Some((piece, color));
+        }
+        BoardBuilder::new().set_board(squares)
+    }
+
+    #[test]
+    fn test_validate_castling_rights() {
+        let builder = builder(&[(E1, Piece::King, Color::White), (H1, Piece::Rook, Color::White), (E8, Piece::King, Color::Black)])
+            .set_castling_rights("KQ".parse().unwrap());
+
+        assert_eq!(Board::try_from(&builder).err(), Some(InvariantViolation::CastlingRightsMismatch(WHITE_QUEEN_SIDE)));
+    }
+
+    #[test]
+    fn test_validate_en_passant_square() {
+        let wrong_rank = builder(&[
+            (E1, Piece::King, Color::White),
+            (E8, Piece::King, Color::Black),
+            (D5, Piece::Pawn, Color::Black),
+            (E5, Piece::Pawn, Color::White),
+        ])
+            .en_passant(Some(D3));
+        let no_pawn = builder(&[(E1, Piece::King, Color::White), (E8, Piece::King, Color::Black), (E5, Piece::Pawn, Color::White)])
+            .en_passant(Some(D6));
+
+        assert_eq!(Board::try_from(&wrong_rank).err(), Some(InvariantViolation::InvalidEnPassantSquare(D3)));
+        assert_eq!(Board::try_from(&no_pawn).err(), Some(InvariantViolation::InvalidEnPassantSquare(D6)));
+    }
+
+    #[test]
+    fn test_to_fen_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            "8/8/8/8/8/8/8/K6k b - - 42 97",
+        ] {
+            assert_eq!(Board::parse_fen(fen).unwrap().to_fen(), fen);
+        }
+    }
+
+    // A fixed capacity writer, standing in for a `no_std` target without an allocator
+    struct StackWriter {
+        buffer: [u8; 128],
+        len: usize,
+    }
+
+    impl std::fmt::Write for StackWriter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            let end = self.len + s.len();
+            self.buffer.get_mut(self.len..end).ok_or(std::fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_fen_without_allocating() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let mut writer = StackWriter { buffer: [0; 128], len: 0 };
+
+        Board::parse_fen(fen).unwrap().write_fen(&mut writer).unwrap();
+
+        assert_eq!(std::str::from_utf8(&writer.buffer[..writer.len]).unwrap(), fen);
+    }
+
+    #[test]
+    fn test_parse_fen_rejects_malformed_boards() {
+        assert!(Board::parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/8 w KQkq - 0 1").is_err());
+        assert!(Board::parse_fen("rnbqkbnrr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_err());
+        assert!(Board::parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq z9 0 1").is_err());
+        assert!(Board::parse_fen("8/8/8/8/8/8/8/K6k b - - 0").is_err());
+        assert!(Board::parse_fen("8/8/8/8/8/8/8/K6k b - - 0 1 extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_fen_rejects_unbacked_castling_and_en_passant() {
+        // No rook on h1, so castling king side would move a rook that isn't there
+        assert!(matches!(
+            Board::parse_fen("4k3/8/8/8/8/8/8/4K3 w K - 0 1"),
+            Err(BoardParseError::InvalidCastlingRights)
+        ));
+        assert!(matches!(
+            Board::parse_fen("4k3/8/8/8/8/8/8/4K3 w - e6 0 1"),
+            Err(BoardParseError::InvalidEnPassantTargetSquare)
+        ));
+        assert!(Board::parse_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").is_ok());
+    }
+}
@@ -0,0 +1,32 @@
+use diesel_core::chess_move::ChessMove;
+use diesel_core::piece::Piece;
+use diesel_core::square::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uci_display() {
+        assert_eq!(ChessMove::new(E2, E4, None).to_string(), "e2e4");
+        assert_eq!(ChessMove::new(B7, A8, Some(Piece::Knight)).to_string(), "b7a8n");
+    }
+
+    #[test]
+    fn test_uci_parse() {
+        assert_eq!("g1f3".parse::<ChessMove>(), Ok(ChessMove::new(G1, F3, None)));
+        assert_eq!("e7e8q".parse::<ChessMove>(), Ok(ChessMove::new(E7, E8, Some(Piece::Queen))));
+        assert!("e7e8k".parse::<ChessMove>().is_err());
+        assert!("e2".parse::<ChessMove>().is_err());
+        assert!("e2e9".parse::<ChessMove>().is_err());
+    }
+
+    #[test]
+    fn test_u16_round_trip() {
+        for chess_move in [ChessMove::new(E2, E4, None), ChessMove::new(H7, H8, Some(Piece::Queen)), ChessMove::new(A2, B1, Some(Piece::Knight))] {
+            assert_eq!(ChessMove::from_u16(chess_move.to_u16()), Some(chess_move));
+        }
+        assert_eq!(ChessMove::new(E2, E4, None).to_u16(), 12 | 28 << 6);
+        assert_eq!(ChessMove::from_u16(7 << 12), None);
+    }
+}
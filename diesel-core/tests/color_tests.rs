@@ -1,4 +1,4 @@
-use chess_core::color::{Color, NUM_COLORS, ALL_COLORS};
+use diesel_core::color::{Color, NUM_COLORS, ALL_COLORS};
 
 #[cfg(test)]
 mod tests {
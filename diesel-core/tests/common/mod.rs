@@ -0,0 +1,56 @@
+use diesel_core::board::builder::BoardBuilder;
+use diesel_core::board::Board;
+use diesel_core::castling::{CastlingRights, BLACK_KING_SIDE, BLACK_QUEEN_SIDE, WHITE_KING_SIDE, WHITE_QUEEN_SIDE};
+use diesel_core::color::Color;
+use diesel_core::piece::{ALL_PIECES, NUM_PIECES, Piece};
+use diesel_core::rank::Rank;
+use diesel_core::square::*;
+use proptest::prelude::*;
+
+const CASTLING_SQUARES: [(u8, Color, Square, Square); 4] = [
+    (WHITE_KING_SIDE, Color::White, E1, H1),
+    (WHITE_QUEEN_SIDE, Color::White, E1, A1),
+    (BLACK_KING_SIDE, Color::Black, E8, H8),
+    (BLACK_QUEEN_SIDE, Color::Black, E8, A8),
+];
+
+/// Random placements of up to `max_pieces` pieces. They need not be legal, which suits tests that only
+/// care about geometry or encoding. Castling rights and the clocks are left at zero unless asked for.
+pub fn arbitrary_board(max_pieces: usize, castling: bool, clocks: bool) -> impl Strategy<Value = Board> {
+    let castling_rights = if castling { 0..16u8 } else { 0..1u8 };
+    let clocks = if clocks { (any::<u8>(), any::<u16>()).boxed() } else { Just((0, 0)).boxed() };
+    (
+        prop::collection::vec((0..64u8, 0..NUM_PIECES, any::<bool>()), 0..max_pieces),
+        any::<bool>(),
+        castling_rights,
+        prop::option::of(0..8u8),
+        clocks,
+    )
+        .prop_map(|(pieces, white_to_move, castling_rights, en_passant_file, (halfmove_clock, fullmove_counter))| {
+            let mut squares = [None; NUM_SQUARES];
+            for (square, piece, white) in pieces {
+                let color = if white { Color::White } else { Color::Black };
+                squares[square as usize] = Some((ALL_PIECES[piece], color));
+            }
+            let side_to_move = if white_to_move { Color::White } else { Color::Black };
+            let en_passant_rank = if white_to_move { Rank::Sixth } else { Rank::Third };
+            let en_passant = en_passant_file.map(|file| Square::new((en_passant_rank as u8) * 8 + file));
+
+            // Keep only the castling rights the placement backs, and drop an en passant square it doesn't
+            let mut castling_rights = CastlingRights::from_bytes(castling_rights);
+            for (right, color, king, rook) in CASTLING_SQUARES {
+                if squares[king.to_index()] != Some((Piece::King, color)) || squares[rook.to_index()] != Some((Piece::Rook, color)) {
+                    castling_rights.remove(right);
+                }
+            }
+
+            let builder = BoardBuilder::new()
+                .set_board(squares)
+                .set_side_to_move(side_to_move)
+                .set_castling_rights(castling_rights)
+                .en_passant(en_passant)
+                .halfmove_clock(halfmove_clock)
+                .fullmove_counter(fullmove_counter);
+            Board::try_from(&builder).unwrap_or_else(|_| builder.en_passant(None).build())
+        })
+}
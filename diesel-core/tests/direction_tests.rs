@@ -0,0 +1,48 @@
+use diesel_core::direction::{Direction, NUM_RAY_DIRECTIONS, ALL_RAY_DIRECTIONS, ALL_KNIGHT_DIRECTIONS};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opposite() {
+        assert_eq!(Direction::North.opposite(), Direction::South);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+        for direction in ALL_RAY_DIRECTIONS {
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+
+    #[test]
+    fn test_offset() {
+        for direction in ALL_RAY_DIRECTIONS {
+            let (file_offset, rank_offset) = direction.get_offset();
+            let (opposite_file_offset, opposite_rank_offset) = direction.opposite().get_offset();
+            assert_eq!(file_offset, -opposite_file_offset);
+            assert_eq!(rank_offset, -opposite_rank_offset);
+            assert_eq!(direction.is_diagonal(), file_offset != 0 && rank_offset != 0);
+        }
+    }
+
+    #[test]
+    fn test_num_ray_directions() {
+        assert_eq!(NUM_RAY_DIRECTIONS, 8);
+    }
+
+    #[test]
+    fn test_knight_directions() {
+        assert_eq!(Direction::NorthNorthEast.opposite(), Direction::SouthSouthWest);
+        assert_eq!(Direction::EastNorthEast.opposite(), Direction::WestSouthWest);
+        for direction in ALL_KNIGHT_DIRECTIONS {
+            let (file_offset, rank_offset) = direction.get_offset();
+            let (opposite_file_offset, opposite_rank_offset) = direction.opposite().get_offset();
+            assert_eq!(file_offset, -opposite_file_offset);
+            assert_eq!(rank_offset, -opposite_rank_offset);
+            assert_eq!(file_offset.abs() + rank_offset.abs(), 3);
+            assert!(direction.is_knight());
+            assert!(!direction.is_diagonal());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use diesel_core::board::{Board, BoardDecodeError, InvariantViolation, MAX_ENCODED_LEN};
+use diesel_core::castling::WHITE_KING_SIDE;
+use proptest::prelude::*;
+
+// Each test file is also built as its own crate, so each loads the shared strategies itself
+#[allow(clippy::duplicate_mod)]
+#[path = "common/mod.rs"]
+mod common;
+
+use common::arbitrary_board;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FENS: [&str; 5] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4K3 b - - 99 300",
+    ];
+
+    #[test]
+    fn test_round_trip() {
+        for fen in FENS {
+            let board = Board::parse_fen(fen).unwrap();
+            let bytes = board.to_bytes();
+
+            assert!(bytes.len() <= MAX_ENCODED_LEN);
+            assert!(bytes.len() < fen.len());
+            assert_eq!(Board::from_bytes(&bytes), Ok(board), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_start_position_size() {
+        assert_eq!(Board::default().to_bytes().len(), 29);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let bytes = Board::default().to_bytes();
+
+        assert_eq!(Board::from_bytes(&bytes[..5]), Err(BoardDecodeError::UnexpectedEnd));
+        assert_eq!(Board::from_bytes(&bytes[..bytes.len() - 1]), Err(BoardDecodeError::UnexpectedEnd));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(Board::from_bytes(&trailing), Err(BoardDecodeError::TrailingBytes));
+
+        let mut bad_piece = bytes.clone();
+        bad_piece[13] = 0xFF;
+        assert_eq!(Board::from_bytes(&bad_piece), Err(BoardDecodeError::InvalidPieceCode(0x0F)));
+
+        let mut bad_flags = bytes.clone();
+        bad_flags[8] |= 0x80;
+        assert!(matches!(Board::from_bytes(&bad_flags), Err(BoardDecodeError::InvalidFlags(_))));
+
+        // White king side castling with no king on e1 or rook on h1
+        let bare_kings = Board::parse_fen("4k3/8/8/8/8/8/8/3K4 w - - 0 1").unwrap().to_bytes();
+        let mut bad_castling = bare_kings.clone();
+        bad_castling[8] |= WHITE_KING_SIDE;
+        assert_eq!(
+            Board::from_bytes(&bad_castling),
+            Err(BoardDecodeError::InvalidPosition(InvariantViolation::CastlingRightsMismatch(WHITE_KING_SIDE)))
+        );
+
+        let mut bad_en_passant = bare_kings;
+        bad_en_passant[9] = 5;
+        assert!(matches!(
+            Board::from_bytes(&bad_en_passant),
+            Err(BoardDecodeError::InvalidPosition(InvariantViolation::InvalidEnPassantSquare(_)))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_round_trip(board in arbitrary_board(40, true, true)) {
+            prop_assert_eq!(Board::from_bytes(&board.to_bytes()), Ok(board));
+        }
+    }
+}
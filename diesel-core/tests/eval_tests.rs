@@ -0,0 +1,57 @@
+use diesel_core::board::Board;
+use diesel_core::eval::{evaluate, get_phase, MAX_PHASE};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(fen: &str) -> i32 {
+        evaluate(&Board::parse_fen(fen).unwrap())
+    }
+
+    #[test]
+    fn test_start_position_is_balanced() {
+        assert_eq!(evaluate(&Board::default()), 0);
+        assert_eq!(get_phase(&Board::default()), MAX_PHASE);
+    }
+
+    #[test]
+    fn test_evaluates_for_side_to_move() {
+        let white = eval("4k3/8/8/8/8/8/8/3QK3 w - - 0 1");
+
+        assert!(white > 800);
+        assert_eq!(eval("4k3/8/8/8/8/8/8/3QK3 b - - 0 1"), -white);
+    }
+
+    #[test]
+    fn test_phase() {
+        assert_eq!(get_phase(&Board::parse_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap()), 0);
+        assert_eq!(get_phase(&Board::parse_fen("r3k3/8/8/8/8/8/8/3QK1N1 w - - 0 1").unwrap()), 7);
+    }
+
+    #[test]
+    fn test_pawn_structure() {
+        // Doubled and isolated against a connected pair
+        assert!(eval("4k3/8/8/8/8/P7/P7/4K3 w - - 0 1") < eval("4k3/8/8/8/8/8/PP6/4K3 w - - 0 1"));
+        // The e-pawn is passed only when the black pawn is away from the neighbouring files
+        assert!(eval("4k3/5p2/8/4P3/8/8/8/4K3 w - - 0 1") < eval("4k3/p7/8/4P3/8/8/8/4K3 w - - 0 1"));
+    }
+
+    #[test]
+    fn test_rook_on_open_file() {
+        assert!(eval("4k3/8/8/8/8/8/P7/R3K3 w - - 0 1") < eval("4k3/8/8/8/8/8/1P6/R3K3 w - - 0 1"));
+    }
+
+    #[test]
+    fn test_bishop_pair() {
+        assert!(eval("4k3/8/8/8/8/8/8/2N1KB2 w - - 0 1") < eval("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1"));
+    }
+
+    #[test]
+    fn test_king_safety() {
+        // The same attackers facing the king, or the same king and pawns on the other wing
+        let exposed = eval("6k1/5ppp/8/6NQ/8/3B4/8/6K1 w - - 0 1");
+        let sheltered = eval("1k6/ppp5/8/6NQ/8/3B4/8/6K1 w - - 0 1");
+        assert!(exposed > sheltered);
+    }
+}
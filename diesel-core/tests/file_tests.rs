@@ -1,4 +1,4 @@
-use chess_core::file::{File, NUM_FILES, ALL_FILES};
+use diesel_core::file::{File, NUM_FILES, ALL_FILES};
 
 #[cfg(test)]
 mod tests {
@@ -3,4 +3,17 @@ mod color_tests;
 mod file_tests;
 mod piece_tests;
 mod rank_tests;
-mod square_tests;
\ No newline at end of file
+mod square_tests;
+mod direction_tests;
+mod tables_tests;
+mod zobrist_tests;
+mod board_tests;
+mod symmetry_tests;
+mod encoding_tests;
+mod serde_tests;
+mod chess_move_tests;
+mod render_tests;
+mod svg_tests;
+mod movegen_tests;
+mod see_tests;
+mod eval_tests;
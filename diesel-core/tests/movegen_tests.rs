@@ -0,0 +1,124 @@
+use diesel_core::bitboard::BitBoard;
+use diesel_core::board::Board;
+use diesel_core::chess_move::ChessMove;
+use diesel_core::color::Color;
+use diesel_core::piece::Piece;
+use diesel_core::square::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    fn perft(board: &Board, depth: u32) -> u64 {
+        let moves = board.get_legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        moves.iter().map(|&chess_move| perft(&board.make_move(chess_move), depth - 1)).sum()
+    }
+
+    fn assert_perft(fen: &str, expected: &[u64]) {
+        let board = Board::parse_fen(fen).unwrap();
+        for (depth, &nodes) in expected.iter().enumerate() {
+            assert_eq!(perft(&board, depth as u32 + 1), nodes, "perft({}) of {}", depth + 1, fen);
+        }
+    }
+
+    #[test]
+    fn test_perft_start_position() {
+        assert_perft("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &[20, 400, 8902, 197281]);
+    }
+
+    #[test]
+    fn test_perft_kiwipete() {
+        assert_perft(KIWIPETE, &[48, 2039, 97862]);
+    }
+
+    #[test]
+    fn test_perft_en_passant_and_pins() {
+        assert_perft("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812, 43238]);
+    }
+
+    #[test]
+    fn test_perft_promotions_and_checks() {
+        assert_perft("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", &[6, 264, 9467]);
+        assert_perft("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1486, 62379]);
+    }
+
+    #[test]
+    fn test_make_move_castles_and_updates_rights() {
+        let board = Board::parse_fen(KIWIPETE).unwrap();
+
+        let castled = board.make_move(ChessMove::new(E1, G1, None));
+
+        assert_eq!(castled.get_piece_at(G1), Some((Piece::King, Color::White)));
+        assert_eq!(castled.get_piece_at(F1), Some((Piece::Rook, Color::White)));
+        assert_eq!(castled.get_piece_at(H1), None);
+        assert_eq!(castled.get_castling_rights().to_string(), "kq");
+        assert_eq!(castled.get_hash(), castled.compute_hash());
+    }
+
+    #[test]
+    fn test_make_move_en_passant() {
+        let board = Board::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+
+        let captured = board.make_move(ChessMove::new(E5, D6, None));
+
+        assert_eq!(captured.get_piece_at(D5), None);
+        assert_eq!(captured.get_halfmove_clock(), 0);
+    }
+
+    #[test]
+    fn test_checks_and_pins() {
+        let board = Board::parse_fen("4k3/8/8/8/8/8/4r3/R3K3 w Q - 0 1").unwrap();
+
+        assert!(board.is_in_check());
+        assert_eq!(board.get_checkers(), BitBoard::from_square(E2));
+        assert!(!board.is_legal(ChessMove::new(E1, C1, None)));
+        assert!(board.is_legal(ChessMove::new(E1, E2, None)));
+
+        let pinned = Board::parse_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        assert_eq!(pinned.get_pinned(), BitBoard::from_square(E2));
+        assert!(pinned.get_legal_moves().iter().all(|chess_move| chess_move.get_from() == E1));
+    }
+
+    #[test]
+    fn test_is_legal_matches_generation() {
+        let fens = [
+            KIWIPETE,
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ];
+        let promotions = [None, Some(Piece::Queen), Some(Piece::Knight), Some(Piece::King), Some(Piece::Pawn)];
+        for fen in fens {
+            let root = Board::parse_fen(fen).unwrap();
+            let children = root.get_legal_moves().iter().map(|&chess_move| root.make_move(chess_move)).collect::<Vec<_>>();
+            for board in children.iter().chain([&root]) {
+                let legal = board.get_legal_moves();
+                for from in ALL_SQUARES {
+                    for to in ALL_SQUARES {
+                        for promotion in promotions {
+                            let chess_move = ChessMove::new(from, to, promotion);
+                            assert_eq!(board.is_legal(chess_move), legal.contains(&chess_move), "{} in {}", chess_move, board.to_fen());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_null_move() {
+        let board = Board::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let passed = board.make_null_move();
+
+        assert_eq!(passed.get_to_move(), Color::Black);
+        assert_eq!(passed.get_en_passant_square(), None);
+        assert_eq!(passed.get_halfmove_clock(), 1);
+        assert_eq!(passed.get_hash(), passed.compute_hash());
+        assert_eq!(passed.make_null_move().get_hash(), Board::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 2 2").unwrap().get_hash());
+    }
+}
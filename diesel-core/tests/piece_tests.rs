@@ -1,5 +1,5 @@
-use chess_core::piece::{Piece, NUM_PIECES, ALL_PIECES};
-use chess_core::color::Color;
+use diesel_core::piece::{Piece, NUM_PIECES, ALL_PIECES};
+use diesel_core::color::Color;
 
 #[cfg(test)]
 mod tests {
@@ -35,6 +35,14 @@ mod tests {
         assert_eq!(Piece::King.to_string(Color::Black), "k");
     }
 
+    #[test]
+    fn test_piece_to_char() {
+        assert_eq!(Piece::Knight.to_char(Color::White), 'N');
+        assert_eq!(Piece::Knight.to_char(Color::Black), 'n');
+        assert_eq!(Piece::Queen.to_fancy_char(Color::White), '♕');
+        assert_eq!(Piece::Queen.to_fancy_char(Color::Black), '♛');
+    }
+
     #[test]
     fn test_all_pieces() {
         assert_eq!(ALL_PIECES, [
@@ -1,4 +1,4 @@
-use chess_core::rank::{Rank, NUM_RANKS, ALL_RANKS};
+use diesel_core::rank::{Rank, NUM_RANKS, ALL_RANKS};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,97 @@
+use diesel_core::board::Board;
+use diesel_core::board::render::{BoardRenderer, PieceStyle};
+use diesel_core::color::Color;
+use diesel_core::square::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINGS_AND_PAWN: &str = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+
+    #[test]
+    fn test_default_matches_display() {
+        let board = Board::default();
+
+        assert_eq!(BoardRenderer::default().display(&board).to_string(), board.to_string());
+    }
+
+    #[test]
+    fn test_letters() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+        let renderer = BoardRenderer::new().set_piece_style(PieceStyle::Letters);
+
+        assert_eq!(renderer.display(&board).to_string(), [
+            "  a b c d e f g h",
+            " -----------------",
+            "8| . . . . k . . .|",
+            "7| . . . . . . . .|",
+            "6| . . . . . . . .|",
+            "5| . . . . . . . .|",
+            "4| . . . . . . . .|",
+            "3| . . . . . . . .|",
+            "2| . . . . P . . .|",
+            "1| . . . . K . . .|",
+            " -----------------",
+            "  a b c d e f g h",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_black_perspective() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+        let renderer = BoardRenderer::new()
+            .set_piece_style(PieceStyle::Letters)
+            .set_perspective(Color::Black);
+
+        let rendered = renderer.display(&board).to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "  h g f e d c b a");
+        assert_eq!(lines[2], "1| . . . K . . . .|");
+        assert_eq!(lines[3], "2| . . . P . . . .|");
+        assert_eq!(lines[9], "8| . . . k . . . .|");
+    }
+
+    #[test]
+    fn test_without_coordinates() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+        let renderer = BoardRenderer::new()
+            .set_piece_style(PieceStyle::Letters)
+            .set_coordinates(false);
+
+        let rendered = renderer.display(&board).to_string();
+
+        assert_eq!(rendered.lines().count(), 10);
+        assert_eq!(rendered.lines().nth(1).unwrap(), " | . . . . k . . .|");
+    }
+
+    #[test]
+    fn test_highlights() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+        let renderer = BoardRenderer::new()
+            .set_piece_style(PieceStyle::Letters)
+            .highlight(E2)
+            .highlight(E4);
+
+        let rendered = renderer.display(&board).to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[6], "4| . . . .*. . . .|");
+        assert_eq!(lines[8], "2| . . . .*P . . .|");
+    }
+
+    #[test]
+    fn test_ansi_colors() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+        let renderer = BoardRenderer::new().set_ansi_colors(true);
+
+        let rendered = renderer.display(&board).to_string();
+
+        assert!(rendered.contains('\x1b'));
+        assert!(rendered.lines().filter(|line| line.contains('|')).all(|line| line.ends_with("\x1b[0m|")));
+
+        let plain = BoardRenderer::new().display(&board).to_string();
+        assert!(!plain.contains('\x1b'));
+    }
+}
@@ -0,0 +1,100 @@
+use diesel_core::board::Board;
+use diesel_core::chess_move::ChessMove;
+use diesel_core::piece::Piece;
+use diesel_core::square::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn see(fen: &str, from: Square, to: Square, promotion: Option<Piece>) -> i32 {
+        Board::parse_fen(fen).unwrap().see(ChessMove::new(from, to, promotion))
+    }
+
+    #[test]
+    fn test_see_undefended_capture() {
+        assert_eq!(see("4k3/8/8/3p4/8/8/8/3QK3 w - - 0 1", D1, D5, None), 100);
+    }
+
+    #[test]
+    fn test_see_defended_pawn_loses_the_queen() {
+        assert_eq!(see("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1", D1, D5, None), -800);
+    }
+
+    #[test]
+    fn test_see_even_pawn_trade() {
+        assert_eq!(see("4k3/8/4p3/3p4/4P3/8/8/4K3 w - - 0 1", E4, D5, None), 0);
+    }
+
+    #[test]
+    fn test_see_x_ray_behind_the_attacker() {
+        assert_eq!(see("4k3/3r4/8/3p4/8/8/3R4/3RK3 w - - 0 1", D2, D5, None), 100);
+    }
+
+    #[test]
+    fn test_see_quiet_move_to_attacked_square() {
+        assert_eq!(see("4k3/8/4p3/8/8/8/8/3QK3 w - - 0 1", D1, D5, None), -900);
+    }
+
+    #[test]
+    fn test_see_king_recaptures_undefended_piece() {
+        assert_eq!(see("8/8/8/4k3/3p4/2p5/8/3QK3 w - - 0 1", D1, D4, None), -800);
+    }
+
+    #[test]
+    fn test_see_en_passant() {
+        assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", E5, D6, None), 100);
+    }
+
+    #[test]
+    fn test_see_promotion() {
+        assert_eq!(see("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", A7, A8, Some(Piece::Queen)), 800);
+    }
+
+    #[test]
+    fn test_noisy_moves_start_position() {
+        assert!(Board::default().get_noisy_moves().is_empty());
+    }
+
+    #[test]
+    fn test_noisy_moves_kiwipete() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let noisy = board.get_noisy_moves();
+        assert_eq!(noisy.len(), 8);
+        assert!(noisy.iter().all(|chess_move| board.get_piece_at(chess_move.get_to()).is_some()));
+    }
+
+    #[test]
+    fn test_noisy_moves_include_quiet_promotions() {
+        let board = Board::parse_fen("8/P6k/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let noisy = board.get_noisy_moves();
+        assert_eq!(noisy.len(), 4);
+        assert!(noisy.iter().all(|chess_move| chess_move.get_promotion().is_some()));
+    }
+
+    #[test]
+    fn test_noisy_moves_are_legal_moves() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let legal = board.get_legal_moves();
+        assert!(board.get_noisy_moves().iter().all(|chess_move| legal.contains(chess_move)));
+    }
+
+    #[test]
+    fn test_noisy_and_quiet_moves_partition_legal_moves() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            "4k3/8/8/8/8/8/4r3/R3K3 w Q - 0 1",
+        ] {
+            let board = Board::parse_fen(fen).unwrap();
+            let noisy = board.get_noisy_moves();
+            let quiet = board.get_quiet_moves();
+            let legal = board.get_legal_moves();
+
+            assert_eq!(noisy.len() + quiet.len(), legal.len(), "{}", fen);
+            assert!(quiet.iter().all(|chess_move| legal.contains(chess_move) && !noisy.contains(chess_move)), "{}", fen);
+            assert!(quiet.iter().all(|chess_move| chess_move.get_promotion().is_none() && board.get_piece_at(chess_move.get_to()).is_none()));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "serde")]
+
+use diesel_core::bitboard::BitBoard;
+use diesel_core::board::Board;
+use diesel_core::castling::CastlingRights;
+use diesel_core::chess_move::ChessMove;
+use diesel_core::color::Color;
+use diesel_core::file::File;
+use diesel_core::piece::Piece;
+use diesel_core::rank::Rank;
+use diesel_core::square::*;
+
+use serde::de::value::{Error, SeqDeserializer};
+use serde::de::{Deserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+// A binary format without a native byte type, which hands bytes over as a sequence
+struct ByteSeq(Vec<u8>);
+
+impl<'de> Deserializer<'de> for ByteSeq {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(SeqDeserializer::new(self.0.into_iter()))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_json_notation() {
+        assert_eq!(serde_json::to_string(&E4).unwrap(), "\"e4\"");
+        assert_eq!(serde_json::to_string(&Piece::Knight).unwrap(), "\"n\"");
+        assert_eq!(serde_json::to_string(&Color::Black).unwrap(), "\"b\"");
+        assert_eq!(serde_json::to_string(&File::C).unwrap(), "\"c\"");
+        assert_eq!(serde_json::to_string(&Rank::Seventh).unwrap(), "\"7\"");
+        assert_eq!(serde_json::to_string(&CastlingRights::from_bytes(0b0101)).unwrap(), "\"Kk\"");
+        assert_eq!(serde_json::to_string(&ChessMove::new(E7, E8, Some(Piece::Queen))).unwrap(), "\"e7e8q\"");
+        assert_eq!(
+            serde_json::to_string(&Board::default()).unwrap(),
+            "\"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\""
+        );
+        assert_eq!(serde_json::to_string(&BitBoard(0x81)).unwrap(), "129");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let board = Board::parse_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 12").unwrap();
+        let json = serde_json::to_string(&(board, ChessMove::new(E5, D6, None), H8)).unwrap();
+
+        assert_eq!(serde_json::from_str::<(Board, ChessMove, Square)>(&json).unwrap(), (board, ChessMove::new(E5, D6, None), H8));
+    }
+
+    #[test]
+    fn test_json_rejects_invalid_values() {
+        assert!(serde_json::from_str::<Square>("\"i9\"").is_err());
+        assert!(serde_json::from_str::<ChessMove>("\"e7e8k\"").is_err());
+        assert!(serde_json::from_str::<Board>("\"not a fen\"").is_err());
+        assert!(serde_json::from_str::<CastlingRights>("\"KX\"").is_err());
+    }
+
+    #[test]
+    fn test_binary_is_compact() {
+        assert_eq!(bincode::serialize(&E4).unwrap(), vec![28]);
+        assert_eq!(bincode::serialize(&ChessMove::new(E2, E4, None)).unwrap().len(), 2);
+
+        let board = Board::default();
+        let bytes = bincode::serialize(&board).unwrap();
+        assert!(bytes.len() < board.to_fen().len());
+        assert_eq!(bincode::deserialize::<Board>(&bytes).unwrap(), board);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let value = (ChessMove::new(A7, B8, Some(Piece::Knight)), Color::White, CastlingRights::from_bytes(0b1010), Rank::Third);
+        let bytes = bincode::serialize(&value).unwrap();
+
+        assert_eq!(bincode::deserialize::<(ChessMove, Color, CastlingRights, Rank)>(&bytes).unwrap(), value);
+        assert!(bincode::deserialize::<Square>(&[64]).is_err());
+    }
+
+    #[test]
+    fn test_board_from_byte_sequence() {
+        let board = Board::default();
+        assert_eq!(Board::deserialize(ByteSeq(board.to_bytes())).unwrap(), board);
+
+        let mut oversized = board.to_bytes();
+        oversized.resize(1 << 20, 0);
+        let error = Board::deserialize(ByteSeq(oversized)).unwrap_err();
+        assert!(error.to_string().starts_with("invalid length"), "{}", error);
+    }
+}
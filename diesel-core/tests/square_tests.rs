@@ -1,6 +1,8 @@
-use chess_core::square::{Square, NUM_SQUARES, ALL_SQUARES};
-use chess_core::rank::Rank;
-use chess_core::file::File;
+use diesel_core::square::*;
+use diesel_core::rank::Rank;
+use diesel_core::file::File;
+use diesel_core::color::Color;
+use diesel_core::direction::Direction;
 
 #[cfg(test)]
 mod tests {
@@ -35,111 +37,111 @@ mod tests {
 
     #[test]
     fn test_square_get_rank() {
-        assert_eq!(Square::A1.get_rank(), Rank::First);
-        assert_eq!(Square::H8.get_rank(), Rank::Eighth);
-        assert_eq!(Square::E4.get_rank(), Rank::Fourth);
+        assert_eq!(A1.get_rank(), Rank::First);
+        assert_eq!(H8.get_rank(), Rank::Eighth);
+        assert_eq!(E4.get_rank(), Rank::Fourth);
     }
 
     #[test]
     fn test_square_get_file() {
-        assert_eq!(Square::A1.get_file(), File::A);
-        assert_eq!(Square::H8.get_file(), File::H);
-        assert_eq!(Square::E4.get_file(), File::E);
+        assert_eq!(A1.get_file(), File::A);
+        assert_eq!(H8.get_file(), File::H);
+        assert_eq!(E4.get_file(), File::E);
     }
 
     #[test]
     fn test_square_to_int() {
-        assert_eq!(Square::A1.to_int(), 0);
-        assert_eq!(Square::H8.to_int(), 63);
-        assert_eq!(Square::E4.to_int(), 28);
+        assert_eq!(A1.to_int(), 0);
+        assert_eq!(H8.to_int(), 63);
+        assert_eq!(E4.to_int(), 28);
     }
 
     #[test]
     fn test_square_consts() {
-        assert_eq!(Square::A1.to_int(), 0);
-        assert_eq!(Square::B1.to_int(), 1);
-        assert_eq!(Square::C1.to_int(), 2);
-        assert_eq!(Square::D1.to_int(), 3);
-        assert_eq!(Square::E1.to_int(), 4);
-        assert_eq!(Square::F1.to_int(), 5);
-        assert_eq!(Square::G1.to_int(), 6);
-        assert_eq!(Square::H1.to_int(), 7);
-
-        assert_eq!(Square::A2.to_int(), 8);
-        assert_eq!(Square::B2.to_int(), 9);
-        assert_eq!(Square::C2.to_int(), 10);
-        assert_eq!(Square::D2.to_int(), 11);
-        assert_eq!(Square::E2.to_int(), 12);
-        assert_eq!(Square::F2.to_int(), 13);
-        assert_eq!(Square::G2.to_int(), 14);
-        assert_eq!(Square::H2.to_int(), 15);
-
-        assert_eq!(Square::A3.to_int(), 16);
-        assert_eq!(Square::B3.to_int(), 17);
-        assert_eq!(Square::C3.to_int(), 18);
-        assert_eq!(Square::D3.to_int(), 19);
-        assert_eq!(Square::E3.to_int(), 20);
-        assert_eq!(Square::F3.to_int(), 21);
-        assert_eq!(Square::G3.to_int(), 22);
-        assert_eq!(Square::H3.to_int(), 23);
-
-        assert_eq!(Square::A4.to_int(), 24);
-        assert_eq!(Square::B4.to_int(), 25);
-        assert_eq!(Square::C4.to_int(), 26);
-        assert_eq!(Square::D4.to_int(), 27);
-        assert_eq!(Square::E4.to_int(), 28);
-        assert_eq!(Square::F4.to_int(), 29);
-        assert_eq!(Square::G4.to_int(), 30);
-        assert_eq!(Square::H4.to_int(), 31);
-
-        assert_eq!(Square::A5.to_int(), 32);
-        assert_eq!(Square::B5.to_int(), 33);
-        assert_eq!(Square::C5.to_int(), 34);
-        assert_eq!(Square::D5.to_int(), 35);
-        assert_eq!(Square::E5.to_int(), 36);
-        assert_eq!(Square::F5.to_int(), 37);
-        assert_eq!(Square::G5.to_int(), 38);
-        assert_eq!(Square::H5.to_int(), 39);
-
-        assert_eq!(Square::A6.to_int(), 40);
-        assert_eq!(Square::B6.to_int(), 41);
-        assert_eq!(Square::C6.to_int(), 42);
-        assert_eq!(Square::D6.to_int(), 43);
-        assert_eq!(Square::E6.to_int(), 44);
-        assert_eq!(Square::F6.to_int(), 45);
-        assert_eq!(Square::G6.to_int(), 46);
-        assert_eq!(Square::H6.to_int(), 47);
-
-        assert_eq!(Square::A7.to_int(), 48);
-        assert_eq!(Square::B7.to_int(), 49);
-        assert_eq!(Square::C7.to_int(), 50);
-        assert_eq!(Square::D7.to_int(), 51);
-        assert_eq!(Square::E7.to_int(), 52);
-        assert_eq!(Square::F7.to_int(), 53);
-        assert_eq!(Square::G7.to_int(), 54);
-        assert_eq!(Square::H7.to_int(), 55);
-
-        assert_eq!(Square::A8.to_int(), 56);
-        assert_eq!(Square::B8.to_int(), 57);
-        assert_eq!(Square::C8.to_int(), 58);
-        assert_eq!(Square::D8.to_int(), 59);
-        assert_eq!(Square::E8.to_int(), 60);
-        assert_eq!(Square::F8.to_int(), 61);
-        assert_eq!(Square::G8.to_int(), 62);
-        assert_eq!(Square::H8.to_int(), 63);
+        assert_eq!(A1.to_int(), 0);
+        assert_eq!(B1.to_int(), 1);
+        assert_eq!(C1.to_int(), 2);
+        assert_eq!(D1.to_int(), 3);
+        assert_eq!(E1.to_int(), 4);
+        assert_eq!(F1.to_int(), 5);
+        assert_eq!(G1.to_int(), 6);
+        assert_eq!(H1.to_int(), 7);
+
+        assert_eq!(A2.to_int(), 8);
+        assert_eq!(B2.to_int(), 9);
+        assert_eq!(C2.to_int(), 10);
+        assert_eq!(D2.to_int(), 11);
+        assert_eq!(E2.to_int(), 12);
+        assert_eq!(F2.to_int(), 13);
+        assert_eq!(G2.to_int(), 14);
+        assert_eq!(H2.to_int(), 15);
+
+        assert_eq!(A3.to_int(), 16);
+        assert_eq!(B3.to_int(), 17);
+        assert_eq!(C3.to_int(), 18);
+        assert_eq!(D3.to_int(), 19);
+        assert_eq!(E3.to_int(), 20);
+        assert_eq!(F3.to_int(), 21);
+        assert_eq!(G3.to_int(), 22);
+        assert_eq!(H3.to_int(), 23);
+
+        assert_eq!(A4.to_int(), 24);
+        assert_eq!(B4.to_int(), 25);
+        assert_eq!(C4.to_int(), 26);
+        assert_eq!(D4.to_int(), 27);
+        assert_eq!(E4.to_int(), 28);
+        assert_eq!(F4.to_int(), 29);
+        assert_eq!(G4.to_int(), 30);
+        assert_eq!(H4.to_int(), 31);
+
+        assert_eq!(A5.to_int(), 32);
+        assert_eq!(B5.to_int(), 33);
+        assert_eq!(C5.to_int(), 34);
+        assert_eq!(D5.to_int(), 35);
+        assert_eq!(E5.to_int(), 36);
+        assert_eq!(F5.to_int(), 37);
+        assert_eq!(G5.to_int(), 38);
+        assert_eq!(H5.to_int(), 39);
+
+        assert_eq!(A6.to_int(), 40);
+        assert_eq!(B6.to_int(), 41);
+        assert_eq!(C6.to_int(), 42);
+        assert_eq!(D6.to_int(), 43);
+        assert_eq!(E6.to_int(), 44);
+        assert_eq!(F6.to_int(), 45);
+        assert_eq!(G6.to_int(), 46);
+        assert_eq!(H6.to_int(), 47);
+
+        assert_eq!(A7.to_int(), 48);
+        assert_eq!(B7.to_int(), 49);
+        assert_eq!(C7.to_int(), 50);
+        assert_eq!(D7.to_int(), 51);
+        assert_eq!(E7.to_int(), 52);
+        assert_eq!(F7.to_int(), 53);
+        assert_eq!(G7.to_int(), 54);
+        assert_eq!(H7.to_int(), 55);
+
+        assert_eq!(A8.to_int(), 56);
+        assert_eq!(B8.to_int(), 57);
+        assert_eq!(C8.to_int(), 58);
+        assert_eq!(D8.to_int(), 59);
+        assert_eq!(E8.to_int(), 60);
+        assert_eq!(F8.to_int(), 61);
+        assert_eq!(G8.to_int(), 62);
+        assert_eq!(H8.to_int(), 63);
     }
 
     #[test]
     fn test_all_squares() {
         assert_eq!(ALL_SQUARES, [
-            Square::A1, Square::B1, Square::C1, Square::D1, Square::E1, Square::F1, Square::G1, Square::H1,
-            Square::A2, Square::B2, Square::C2, Square::D2, Square::E2, Square::F2, Square::G2, Square::H2,
-            Square::A3, Square::B3, Square::C3, Square::D3, Square::E3, Square::F3, Square::G3, Square::H3,
-            Square::A4, Square::B4, Square::C4, Square::D4, Square::E4, Square::F4, Square::G4, Square::H4,
-            Square::A5, Square::B5, Square::C5, Square::D5, Square::E5, Square::F5, Square::G5, Square::H5,
-            Square::A6, Square::B6, Square::C6, Square::D6, Square::E6, Square::F6, Square::G6, Square::H6,
-            Square::A7, Square::B7, Square::C7, Square::D7, Square::E7, Square::F7, Square::G7, Square::H7,
-            Square::A8, Square::B8, Square::C8, Square::D8, Square::E8, Square::F8, Square::G8, Square::H8,
+            A1, B1, C1, D1, E1, F1, G1, H1,
+            A2, B2, C2, D2, E2, F2, G2, H2,
+            A3, B3, C3, D3, E3, F3, G3, H3,
+            A4, B4, C4, D4, E4, F4, G4, H4,
+            A5, B5, C5, D5, E5, F5, G5, H5,
+            A6, B6, C6, D6, E6, F6, G6, H6,
+            A7, B7, C7, D7, E7, F7, G7, H7,
+            A8, B8, C8, D8, E8, F8, G8, H8,
         ]);
     }
 
@@ -147,4 +149,77 @@ mod tests {
     fn test_num_squares() {
         assert_eq!(NUM_SQUARES, 64);
     }
+
+    #[test]
+    fn test_square_offset_off_board() {
+        assert_eq!(A1.offset(-1, 0), None);
+        assert_eq!(A1.offset(0, -1), None);
+        assert_eq!(H8.offset(1, 1), None);
+        assert_eq!(E4.offset(-2, 3), Some(C7));
+    }
+
+    #[test]
+    fn test_square_shift() {
+        assert_eq!(E4.shift(Direction::North), Some(E5));
+        assert_eq!(E4.shift(Direction::SouthWest), Some(D3));
+        assert_eq!(G1.shift(Direction::NorthNorthEast), Some(H3));
+        assert_eq!(G1.shift(Direction::EastNorthEast), None);
+        assert_eq!(A1.shift(Direction::West), None);
+        assert_eq!(H8.up(), None);
+        assert_eq!(A1.down(), None);
+    }
+
+    #[test]
+    fn test_square_relative_rank() {
+        assert_eq!(E2.relative_rank(Color::White), Rank::Second);
+        assert_eq!(E7.relative_rank(Color::Black), Rank::Second);
+        assert_eq!(A1.relative_rank(Color::Black), Rank::Eighth);
+    }
+
+    #[test]
+    fn test_square_flip() {
+        assert_eq!(A1.flip(), A8);
+        assert_eq!(E2.flip(), E7);
+        for square in ALL_SQUARES {
+            assert_eq!(square.flip().flip(), square);
+        }
+    }
+
+    #[test]
+    fn test_square_color() {
+        assert_eq!(A1.color(), SquareColor::Dark);
+        assert_eq!(H1.color(), SquareColor::Light);
+        assert!(D1.is_light());
+        assert!(!E1.is_light());
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance(A1, H8), 7);
+        assert_eq!(distance(E4, F6), 2);
+        assert_eq!(distance(C3, C3), 0);
+    }
+
+    #[test]
+    fn test_aligned() {
+        assert!(aligned(A1, C3, H8));
+        assert!(aligned(E1, E4, E8));
+        assert!(aligned(A4, H4, D4));
+        assert!(!aligned(A1, C3, B4));
+        assert!(!aligned(E1, F3, G5));
+        assert!(!aligned(A1, B3, C5));
+    }
+
+    #[test]
+    fn test_iter_ray() {
+        assert_eq!(E4.iter_ray(Direction::NorthEast).collect::<Vec<Square>>(), vec![F5, G6, H7]);
+        assert_eq!(A1.iter_ray(Direction::South).count(), 0);
+        assert_eq!(B1.iter_ray(Direction::NorthNorthEast).collect::<Vec<Square>>(), vec![C3, D5, E7]);
+    }
+
+    #[test]
+    fn test_square_from_str() {
+        assert_eq!("e4".parse::<Square>(), Ok(E4));
+        assert_eq!("z9".parse::<Square>(), Err(()));
+    }
 }
@@ -0,0 +1,93 @@
+#![cfg(feature = "alloc")]
+
+use diesel_core::board::Board;
+use diesel_core::board::svg::SvgRenderer;
+use diesel_core::color::Color;
+use diesel_core::square::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINGS_AND_PAWN: &str = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+
+    fn count(svg: &str, pattern: &str) -> usize {
+        svg.matches(pattern).count()
+    }
+
+    #[test]
+    fn test_document() {
+        let svg = SvgRenderer::new().render(&Board::default());
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("width=\"400\" height=\"400\""));
+        assert_eq!(count(&svg, "fill=\"#f0d9b5\""), 32);
+        assert_eq!(count(&svg, "fill=\"#b58863\""), 32);
+        assert_eq!(count(&svg, "<use "), 32);
+        // One glyph per piece and color
+        assert_eq!(count(&svg, "<g id="), 12);
+    }
+
+    #[test]
+    fn test_only_used_glyphs_are_defined() {
+        let svg = SvgRenderer::new().render(&Board::parse_fen(KINGS_AND_PAWN).unwrap());
+
+        assert_eq!(count(&svg, "<g id="), 3);
+        assert!(svg.contains("<g id=\"wk\""));
+        assert!(svg.contains("<g id=\"wp\""));
+        assert!(svg.contains("<g id=\"bk\""));
+    }
+
+    #[test]
+    fn test_perspective() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+
+        let white = SvgRenderer::new().set_coordinates(false).render(&board);
+        let black = SvgRenderer::new().set_coordinates(false).set_perspective(Color::Black).render(&board);
+
+        // e1 is the fifth square of the bottom row for White and the fourth of the top row for Black
+        assert!(white.contains("<use xlink:href=\"#wk\" x=\"180\" y=\"315\"/>"));
+        assert!(black.contains("<use xlink:href=\"#wk\" x=\"135\" y=\"0\"/>"));
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+
+        let with = SvgRenderer::new().render(&board);
+        let without = SvgRenderer::new().set_coordinates(false).render(&board);
+
+        assert_eq!(count(&with, "<text "), 16);
+        assert_eq!(count(&without, "<text "), 0);
+        assert!(without.contains("width=\"360\" height=\"360\""));
+    }
+
+    #[test]
+    fn test_highlights_and_arrows() {
+        let board = Board::parse_fen(KINGS_AND_PAWN).unwrap();
+        let renderer = SvgRenderer::new()
+            .set_coordinates(false)
+            .highlight(E2)
+            .highlight(E4)
+            .arrow(E2, E4);
+
+        let svg = renderer.render(&board);
+
+        assert_eq!(count(&svg, "fill-opacity=\"0.5\""), 2);
+        assert_eq!(count(&svg, "<line "), 1);
+        // The arrow head points at the centre of e4
+        assert!(svg.contains("<polygon points=\"202.5,202.5 "));
+    }
+
+    #[test]
+    fn test_caption_is_escaped() {
+        let renderer = SvgRenderer::new().set_caption("1. e4 <e5> & \"more\"");
+
+        let svg = renderer.render(&Board::default());
+
+        assert!(svg.contains("width=\"400\" height=\"430\""));
+        assert!(svg.contains(">1. e4 &lt;e5&gt; &amp; &quot;more&quot;</text>"));
+        assert_eq!(renderer.get_caption(), Some("1. e4 <e5> & \"more\""));
+    }
+}
@@ -0,0 +1,101 @@
+use diesel_core::board::Board;
+use diesel_core::color::Color;
+use diesel_core::eval::{evaluate, PIECE_VALUES};
+use diesel_core::piece::Piece;
+use diesel_core::square::*;
+use proptest::prelude::*;
+
+// Each test file is also built as its own crate, so each loads the shared strategies itself
+#[allow(clippy::duplicate_mod)]
+#[path = "common/mod.rs"]
+mod common;
+
+use common::arbitrary_board;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Material plus a rank bonus, scored for the side to move. Stands in for a real evaluation
+    fn reference_eval(board: &Board) -> i32 {
+        ALL_SQUARES
+            .iter()
+            .filter_map(|&square| board.get_piece_at(square).map(|(piece, color)| (square, piece, color)))
+            .map(|(square, piece, color)| {
+                let score = PIECE_VALUES[piece.to_index()] + 5 * square.relative_rank(color) as i32;
+                if color == board.get_to_move() { score } else { -score }
+            })
+            .sum()
+    }
+
+    /// Asserts that `evaluate` scores a position and its color-flipped twin identically. Any
+    /// side-to-move relative evaluation must pass this for every position.
+    pub fn assert_color_symmetric(board: &Board, evaluate: impl Fn(&Board) -> i32) {
+        assert_eq!(evaluate(board), evaluate(&board.flip_colors()), "asymmetric evaluation of\n{}", board);
+    }
+
+    #[test]
+    fn test_flip_colors_start_position() {
+        let board = Board::default();
+        let flipped = board.flip_colors();
+
+        assert_eq!(flipped.get_piece_at(E8), Some((Piece::King, Color::Black)));
+        assert_eq!(flipped.get_piece_at(E1), Some((Piece::King, Color::White)));
+        assert_eq!(flipped.get_to_move(), Color::Black);
+        assert_eq!(flipped.get_castling_rights(), board.get_castling_rights());
+    }
+
+    #[test]
+    fn test_flip_colors_en_passant() {
+        let board = Board::parse_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let flipped = board.flip_colors();
+
+        assert_eq!(flipped.get_en_passant_square(), Some(D3));
+        assert_eq!(flipped.get_piece_at(E4), Some((Piece::Pawn, Color::Black)));
+        assert_eq!(flipped.get_hash(), flipped.compute_hash());
+    }
+
+    #[test]
+    fn test_mirror_requires_no_castling() {
+        let board = Board::default();
+
+        assert!(board.mirror_horizontal().is_none());
+        assert!(board.rotate().is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_flip_colors_is_involution(board in arbitrary_board(32, true, false)) {
+            prop_assert_eq!(board.flip_colors().flip_colors(), board);
+        }
+
+        #[test]
+        fn prop_mirror_and_rotate_are_involutions(board in arbitrary_board(32, false, false)) {
+            let mirrored = board.mirror_horizontal().unwrap();
+            let rotated = board.rotate().unwrap();
+
+            prop_assert_eq!(mirrored.mirror_horizontal().unwrap(), board);
+            prop_assert_eq!(rotated.rotate().unwrap(), board);
+            prop_assert_eq!(rotated, mirrored.flip_colors());
+        }
+
+        #[test]
+        fn prop_transforms_keep_board_consistent(board in arbitrary_board(32, true, false)) {
+            let flipped = board.flip_colors();
+
+            prop_assert!(flipped.is_mailbox_consistent());
+            prop_assert_eq!(flipped.get_hash(), flipped.compute_hash());
+            prop_assert_eq!(flipped.get_occupancy_bitboard(), board.get_occupancy_bitboard().flip_vertical());
+        }
+
+        #[test]
+        fn prop_reference_eval_is_color_symmetric(board in arbitrary_board(32, true, false)) {
+            assert_color_symmetric(&board, reference_eval);
+        }
+
+        #[test]
+        fn prop_evaluate_is_color_symmetric(board in arbitrary_board(32, true, false)) {
+            assert_color_symmetric(&board, evaluate);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use diesel_core::bitboard::{BitBoard, EMPTY_BITBOARD};
+use diesel_core::color::Color;
+use diesel_core::direction::Direction;
+use diesel_core::square::*;
+use diesel_core::tables::{
+    get_between, get_bishop_moves, get_distance, get_king_moves, get_knight_moves, get_line, get_manhattan_distance,
+    get_pawn_attacks, get_pawn_moves, get_queen_moves, get_ray, get_rook_moves,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitboard_of(squares: &[Square]) -> BitBoard {
+        squares.iter().fold(EMPTY_BITBOARD, |bitboard, square| bitboard | BitBoard::from_square(*square))
+    }
+
+    #[test]
+    fn test_ray() {
+        assert_eq!(get_ray(Direction::North, E4), bitboard_of(&[E5, E6, E7, E8]));
+        assert_eq!(get_ray(Direction::SouthWest, E4), bitboard_of(&[D3, C2, B1]));
+        assert_eq!(get_ray(Direction::West, A1), EMPTY_BITBOARD);
+    }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(get_between(A1, H8), bitboard_of(&[B2, C3, D4, E5, F6, G7]));
+        assert_eq!(get_between(H8, A1), get_between(A1, H8));
+        assert_eq!(get_between(E1, E4), bitboard_of(&[E2, E3]));
+        assert_eq!(get_between(E1, E2), EMPTY_BITBOARD);
+        assert_eq!(get_between(E1, F3), EMPTY_BITBOARD);
+        assert_eq!(get_between(E1, E1), EMPTY_BITBOARD);
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(get_line(C3, E5), bitboard_of(&[A1, B2, C3, D4, E5, F6, G7, H8]));
+        assert_eq!(get_line(B1, B2), bitboard_of(&[B1, B2, B3, B4, B5, B6, B7, B8]));
+        assert_eq!(get_line(E1, F3), EMPTY_BITBOARD);
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(get_distance(A1, H8), 7);
+        assert_eq!(get_distance(E4, F6), 2);
+        assert_eq!(get_distance(E4, E4), 0);
+        assert_eq!(get_manhattan_distance(A1, H8), 14);
+        assert_eq!(get_manhattan_distance(E4, F6), 3);
+    }
+
+    #[test]
+    fn test_leapers() {
+        assert_eq!(get_king_moves(A1), bitboard_of(&[A2, B1, B2]));
+        assert_eq!(get_knight_moves(B1), bitboard_of(&[A3, C3, D2]));
+        assert_eq!(get_pawn_attacks(Color::White, A2), bitboard_of(&[B3]));
+        assert_eq!(get_pawn_attacks(Color::Black, E5), bitboard_of(&[D4, F4]));
+        assert_eq!(get_pawn_moves(Color::White, E2), bitboard_of(&[E3, E4]));
+        assert_eq!(get_pawn_moves(Color::Black, E6), bitboard_of(&[E5]));
+    }
+
+    #[test]
+    fn test_sliders() {
+        let blockers = bitboard_of(&[E6, C4, B7, G2]);
+        assert_eq!(get_rook_moves(E4, blockers), bitboard_of(&[E5, E6, E3, E2, E1, D4, C4, F4, G4, H4]));
+        assert_eq!(get_bishop_moves(E4, blockers), bitboard_of(&[D5, C6, B7, F5, G6, H7, D3, C2, B1, F3, G2]));
+        assert_eq!(get_queen_moves(E4, blockers), get_rook_moves(E4, blockers) | get_bishop_moves(E4, blockers));
+        assert_eq!(get_rook_moves(A1, EMPTY_BITBOARD), get_ray(Direction::North, A1) | get_ray(Direction::East, A1));
+    }
+}
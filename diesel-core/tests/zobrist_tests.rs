@@ -0,0 +1,72 @@
+use std::str::FromStr;
+use diesel_core::board::Board;
+use diesel_core::castling::{CastlingRights, NO_CASTLING, WHITE_KING_SIDE, WHITE_CASTLING};
+use diesel_core::zobrist::{get_castling_key, get_side_key};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_matches_recomputation() {
+        let board = Board::default();
+        assert_eq!(board.get_hash(), board.compute_hash());
+        assert_ne!(board.get_hash(), 0);
+    }
+
+    #[test]
+    fn test_side_to_move_changes_hash() {
+        let white = Board::from_str("8/8/8/8/8/8/8/K6k w - - 0 1").unwrap();
+        let black = Board::from_str("8/8/8/8/8/8/8/K6k b - - 0 1").unwrap();
+        assert_eq!(white.get_hash() ^ black.get_hash(), get_side_key());
+    }
+
+    #[test]
+    fn test_castling_key_combines_rights() {
+        assert_eq!(get_castling_key(CastlingRights::from_bytes(NO_CASTLING)), 0);
+        assert_ne!(
+            get_castling_key(CastlingRights::from_bytes(WHITE_CASTLING)),
+            get_castling_key(CastlingRights::from_bytes(WHITE_KING_SIDE))
+        );
+    }
+
+    #[test]
+    fn test_en_passant_only_hashed_when_capturable() {
+        let without = Board::from_str("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        let uncapturable = Board::from_str("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        assert_eq!(without.get_hash(), uncapturable.get_hash());
+
+        let without = Board::from_str("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3").unwrap();
+        let capturable = Board::from_str("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+        assert_ne!(without.get_hash(), capturable.get_hash());
+    }
+
+    #[test]
+    fn test_polyglot_reference_keys() {
+        // The reference positions and keys from the Polyglot book format specification
+        let positions = [
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 0x463b96181691fc9c),
+            ("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", 0x823c9b50fd114196),
+            ("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", 0x0756b94461c50fb0),
+            ("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2", 0x662fafb965db29d4),
+            ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", 0x22a48b5a8e47ff78),
+            ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3", 0x652a607ca3f242c1),
+            ("rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4", 0x00fdd303c946bdd9),
+            ("rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3", 0x3c8123ea7b067637),
+            ("rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4", 0x5c3f9b829b279560),
+        ];
+        for (fen, key) in positions {
+            let board = Board::from_str(fen).unwrap();
+            assert_eq!(board.get_hash(), key, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_polyglot_keys_after_moves() {
+        let mut board = Board::default();
+        for (uci, key) in [("e2e4", 0x823c9b50fd114196), ("d7d5", 0x0756b94461c50fb0), ("e4e5", 0x662fafb965db29d4), ("f7f5", 0x22a48b5a8e47ff78)] {
+            board = board.make_move(uci.parse().unwrap());
+            assert_eq!(board.get_hash(), key, "after {}", uci);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::score::Score;
+
+/// Which search extensions are enabled. Check and singular extensions are on by default, recapture and
+/// passed pawn extensions are off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extensions {
+    check: bool,
+    singular: bool,
+    recapture: bool,
+    passed_pawn: bool,
+}
+
+impl Extensions {
+    pub fn new() -> Self {
+        Self {
+            check: true,
+            singular: true,
+            recapture: false,
+            passed_pawn: false,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self {
+            check: false,
+            singular: false,
+            recapture: false,
+            passed_pawn: false,
+        }
+    }
+
+    pub fn get_check(&self) -> bool {
+        self.check
+    }
+
+    /// Extends moves that give check.
+    pub fn set_check(mut self, enabled: bool) -> Self {
+        self.check = enabled;
+        self
+    }
+
+    pub fn get_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Extends the TT move when a reduced search of every other move fails well below its score.
+    pub fn set_singular(mut self, enabled: bool) -> Self {
+        self.singular = enabled;
+        self
+    }
+
+    pub fn get_recapture(&self) -> bool {
+        self.recapture
+    }
+
+    /// Extends captures on the square the previous move captured on.
+    pub fn set_recapture(mut self, enabled: bool) -> Self {
+        self.recapture = enabled;
+        self
+    }
+
+    pub fn get_passed_pawn(&self) -> bool {
+        self.passed_pawn
+    }
+
+    /// Extends pawn pushes to the seventh rank, where no pawn can stop them.
+    pub fn set_passed_pawn(mut self, enabled: bool) -> Self {
+        self.passed_pawn = enabled;
+        self
+    }
+}
+
+impl Default for Extensions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The shallowest depth singular extensions are tried at.
+pub const SINGULAR_DEPTH: i32 = 6;
+/// How much shallower than the node the TT entry may be and still be trusted for a singular extension.
+pub const SINGULAR_TT_DEPTH_MARGIN: i32 = 3;
+
+/// The score every other move must fail to reach for the TT move to be singular.
+pub const fn get_singular_beta(tt_score: Score, depth: i32) -> Score {
+    tt_score - 2 * depth
+}
+
+/// Extensions stop once a line is this many times longer than the iteration's depth, so that checks
+/// can't extend the search without end.
+pub const EXTENSION_PLY_FACTOR: usize = 2;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let extensions = Extensions::default();
+
+        assert!(extensions.get_check());
+        assert!(extensions.get_singular());
+        assert!(!extensions.get_recapture());
+        assert!(!extensions.get_passed_pawn());
+        assert_eq!(Extensions::none().set_check(true).set_singular(true), extensions);
+    }
+}
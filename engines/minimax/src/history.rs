@@ -0,0 +1,158 @@
+use diesel_core::board::Board;
+use diesel_core::chess_move::ChessMove;
+use diesel_core::color::NUM_COLORS;
+use diesel_core::piece::{Piece, NUM_PIECES};
+use diesel_core::square::{Square, NUM_SQUARES};
+
+use crate::score::MAX_PLY;
+
+// History scores saturate towards this bound, so recent cutoffs outweigh old ones
+const MAX_HISTORY: i32 = 16_384;
+const MAX_BONUS: i32 = 1_200;
+
+/// The piece that made a move and the square it moved to, which is what countermoves and continuation
+/// history key on.
+pub type PieceTo = (Piece, Square);
+
+type ContinuationTable = [[[i32; NUM_SQUARES]; NUM_PIECES]; NUM_SQUARES];
+
+/// Quiet move statistics gathered from beta cutoffs, used to order the quiet moves.
+pub struct History {
+    // Indexed by side to move, from and to square
+    butterfly: Vec<[[i32; NUM_SQUARES]; NUM_SQUARES]>,
+    // Indexed by the previous move's piece and square, then this move's piece and square
+    continuation: Vec<ContinuationTable>,
+    killers: [[Option<ChessMove>; 2]; MAX_PLY],
+    // Indexed by side to move and the previous move's piece and square
+    counter_moves: Vec<[[Option<ChessMove>; NUM_SQUARES]; NUM_PIECES]>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            butterfly: vec![[[0; NUM_SQUARES]; NUM_SQUARES]; NUM_COLORS],
+            continuation: vec![[[[0; NUM_SQUARES]; NUM_PIECES]; NUM_SQUARES]; NUM_PIECES],
+            killers: [[None; 2]; MAX_PLY],
+            counter_moves: vec![[[None; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn clear_killers(&mut self, ply: usize) {
+        self.killers[ply] = [None; 2];
+    }
+
+    pub fn get_killers(&self, ply: usize) -> [Option<ChessMove>; 2] {
+        self.killers[ply]
+    }
+
+    pub fn get_counter_move(&self, board: &Board, previous: Option<PieceTo>) -> Option<ChessMove> {
+        let (piece, to) = previous?;
+        self.counter_moves[board.get_to_move().to_index()][piece.to_index()][to.to_index()]
+    }
+
+    /// Returns the butterfly and continuation history score of a quiet move.
+    pub fn get_quiet_score(&self, board: &Board, chess_move: ChessMove, previous: Option<PieceTo>) -> i32 {
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+        let mut score = self.butterfly[board.get_to_move().to_index()][from.to_index()][to.to_index()];
+        if let (Some((previous_piece, previous_to)), Some((piece, _))) = (previous, board.get_piece_at(from)) {
+            score += self.continuation[previous_piece.to_index()][previous_to.to_index()][piece.to_index()][to.to_index()];
+        }
+        score
+    }
+
+    /// Rewards the quiet move that caused a beta cutoff at `ply` and penalises the quiet moves searched
+    /// before it.
+    pub fn update_quiet(&mut self, board: &Board, best: ChessMove, tried: &[ChessMove], previous: Option<PieceTo>, ply: usize, depth: i32) {
+        let bonus = (depth * depth).min(MAX_BONUS);
+        self.update_quiet_score(board, best, previous, bonus);
+        for &chess_move in tried.iter().filter(|&&chess_move| chess_move != best) {
+            self.update_quiet_score(board, chess_move, previous, -bonus);
+        }
+
+        if self.killers[ply][0] != Some(best) {
+            self.killers[ply] = [Some(best), self.killers[ply][0]];
+        }
+        if let Some((piece, to)) = previous {
+            self.counter_moves[board.get_to_move().to_index()][piece.to_index()][to.to_index()] = Some(best);
+        }
+    }
+
+    fn update_quiet_score(&mut self, board: &Board, chess_move: ChessMove, previous: Option<PieceTo>, bonus: i32) {
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+        apply_bonus(&mut self.butterfly[board.get_to_move().to_index()][from.to_index()][to.to_index()], bonus);
+        if let (Some((previous_piece, previous_to)), Some((piece, _))) = (previous, board.get_piece_at(from)) {
+            apply_bonus(
+                &mut self.continuation[previous_piece.to_index()][previous_to.to_index()][piece.to_index()][to.to_index()],
+                bonus,
+            );
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Scales the bonus down as the entry approaches the bound, keeping it within MAX_HISTORY
+fn apply_bonus(entry: &mut i32, bonus: i32) {
+    *entry += bonus - *entry * bonus.abs() / MAX_HISTORY;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_core::square::*;
+
+    #[test]
+    fn test_cutoff_updates_history_killers_and_countermoves() {
+        let board = Board::default();
+        let mut history = History::new();
+        let best = ChessMove::new(G1, F3, None);
+        let tried = [ChessMove::new(A2, A3, None), best];
+        let previous = Some((Piece::Pawn, E5));
+
+        history.update_quiet(&board, best, &tried, previous, 3, 4);
+
+        assert!(history.get_quiet_score(&board, best, previous) > history.get_quiet_score(&board, best, None));
+        assert!(history.get_quiet_score(&board, best, None) > 0);
+        assert!(history.get_quiet_score(&board, tried[0], None) < 0);
+        assert_eq!(history.get_killers(3), [Some(best), None]);
+        assert_eq!(history.get_counter_move(&board, previous), Some(best));
+        assert_eq!(history.get_counter_move(&board, None), None);
+    }
+
+    #[test]
+    fn test_killers_keep_the_two_latest_moves() {
+        let board = Board::default();
+        let mut history = History::new();
+        let moves = [ChessMove::new(A2, A3, None), ChessMove::new(B2, B3, None), ChessMove::new(C2, C3, None)];
+        for chess_move in moves {
+            history.update_quiet(&board, chess_move, &[], None, 0, 1);
+            history.update_quiet(&board, chess_move, &[], None, 0, 1);
+        }
+
+        assert_eq!(history.get_killers(0), [Some(moves[2]), Some(moves[1])]);
+        history.clear_killers(0);
+        assert_eq!(history.get_killers(0), [None, None]);
+    }
+
+    #[test]
+    fn test_history_saturates() {
+        let board = Board::default();
+        let mut history = History::new();
+        let chess_move = ChessMove::new(E2, E4, None);
+        for _ in 0..1_000 {
+            history.update_quiet(&board, chess_move, &[], None, 0, 30);
+        }
+
+        assert!(history.get_quiet_score(&board, chess_move, None) <= MAX_HISTORY);
+    }
+}
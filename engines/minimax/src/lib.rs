@@ -1,14 +1,8 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub mod extensions;
+pub mod history;
+pub mod move_picker;
+pub mod pruning;
+pub mod score;
+pub mod search;
+pub mod time;
+pub mod tt;
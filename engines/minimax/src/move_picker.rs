@@ -0,0 +1,257 @@
+use diesel_core::board::Board;
+use diesel_core::chess_move::ChessMove;
+use diesel_core::eval::PIECE_VALUES;
+use diesel_core::move_list::{MoveList, MAX_MOVES};
+use diesel_core::piece::Piece;
+
+use crate::history::{History, PieceTo};
+
+/// The stages a [`MovePicker`] goes through, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    HashMove,
+    GenerateCaptures,
+    GoodCaptures,
+    GenerateQuiets,
+    Killers,
+    CounterMove,
+    ScoreQuiets,
+    Quiets,
+    BadCaptures,
+    Done,
+}
+
+/// Yields the moves of a position one at a time, best first, generating and scoring each group of moves
+/// only once the previous ones have been searched: the hash move, captures that don't lose material by
+/// MVV-LVA, killers, the countermove, quiets by history, and finally the losing captures.
+pub struct MovePicker {
+    stage: Stage,
+    captures_only: bool,
+    hash_move: Option<ChessMove>,
+    killers: [Option<ChessMove>; 2],
+    counter_move: Option<ChessMove>,
+    previous: Option<PieceTo>,
+    // The captures and then the quiets, with the moves before `index` already picked
+    moves: MoveList,
+    scores: [i32; MAX_MOVES],
+    index: usize,
+    bad_captures: MoveList,
+}
+
+impl MovePicker {
+    /// Picks every legal move. The hash move must be legal in the position.
+    pub fn new(hash_move: Option<ChessMove>, killers: [Option<ChessMove>; 2], counter_move: Option<ChessMove>, previous: Option<PieceTo>) -> Self {
+        Self {
+            stage: Stage::HashMove,
+            captures_only: false,
+            hash_move,
+            killers,
+            counter_move,
+            previous,
+            moves: MoveList::new(),
+            scores: [0; MAX_MOVES],
+            index: 0,
+            bad_captures: MoveList::new(),
+        }
+    }
+
+    /// Picks only the captures and promotions that don't lose material, for the quiescence search.
+    pub fn new_captures() -> Self {
+        Self {
+            stage: Stage::GenerateCaptures,
+            captures_only: true,
+            ..Self::new(None, [None; 2], None, None)
+        }
+    }
+
+    pub fn get_stage(&self) -> Stage {
+        self.stage
+    }
+
+    pub fn next(&mut self, board: &Board, history: &History) -> Option<ChessMove> {
+        loop {
+            match self.stage {
+                Stage::HashMove => {
+                    self.stage = Stage::GenerateCaptures;
+                    if self.hash_move.is_some() {
+                        return self.hash_move;
+                    }
+                }
+                Stage::GenerateCaptures => {
+                    self.set_moves(board.get_noisy_moves(), |chess_move| get_mvv_lva(board, chess_move));
+                    self.stage = Stage::GoodCaptures;
+                }
+                Stage::GoodCaptures => {
+                    while let Some(chess_move) = self.pick_best() {
+                        if Some(chess_move) == self.hash_move {
+                            continue;
+                        }
+                        if board.see(chess_move) < 0 {
+                            self.bad_captures.push(chess_move);
+                            continue;
+                        }
+                        return Some(chess_move);
+                    }
+                    self.stage = if self.captures_only { Stage::Done } else { Stage::GenerateQuiets };
+                }
+                Stage::GenerateQuiets => {
+                    // Generated before the killers and countermove so they can be checked against it
+                    self.moves = board.get_quiet_moves();
+                    self.index = 0;
+                    self.stage = Stage::Killers;
+                }
+                Stage::Killers => {
+                    // The index counts the killers tried until the quiets are scored
+                    while self.index < self.killers.len() {
+                        let killer = self.killers[self.index];
+                        self.index += 1;
+                        if killer.is_some_and(|killer| self.is_new_quiet(killer, Stage::Killers)) {
+                            return killer;
+                        }
+                    }
+                    self.stage = Stage::CounterMove;
+                }
+                Stage::CounterMove => {
+                    self.stage = Stage::ScoreQuiets;
+                    if self.counter_move.is_some_and(|counter_move| self.is_new_quiet(counter_move, Stage::CounterMove)) {
+                        return self.counter_move;
+                    }
+                }
+                Stage::ScoreQuiets => {
+                    let previous = self.previous;
+                    self.set_moves(self.moves, |chess_move| history.get_quiet_score(board, chess_move, previous));
+                    self.stage = Stage::Quiets;
+                }
+                Stage::Quiets => {
+                    while let Some(chess_move) = self.pick_best() {
+                        if self.is_new_quiet(chess_move, Stage::Quiets) {
+                            return Some(chess_move);
+                        }
+                    }
+                    self.index = 0;
+                    self.stage = Stage::BadCaptures;
+                }
+                Stage::BadCaptures => {
+                    if let Some(&chess_move) = self.bad_captures.get(self.index) {
+                        self.index += 1;
+                        return Some(chess_move);
+                    }
+                    self.stage = Stage::Done;
+                }
+                Stage::Done => return None,
+            }
+        }
+    }
+
+    fn set_moves(&mut self, moves: MoveList, score: impl Fn(ChessMove) -> i32) {
+        self.moves = moves;
+        for (i, &chess_move) in self.moves.iter().enumerate() {
+            self.scores[i] = score(chess_move);
+        }
+        self.index = 0;
+    }
+
+    // Selection sort one move at a time, as a cutoff usually comes before the list is exhausted
+    fn pick_best(&mut self) -> Option<ChessMove> {
+        let best = (self.index..self.moves.len()).max_by_key(|&i| self.scores[i])?;
+        self.moves.swap(self.index, best);
+        self.scores.swap(self.index, best);
+        self.index += 1;
+        Some(self.moves[self.index - 1])
+    }
+
+    // Whether a quiet move is legal and wasn't already picked in a stage before `stage`
+    fn is_new_quiet(&self, chess_move: ChessMove, stage: Stage) -> bool {
+        let picked = Some(chess_move) == self.hash_move
+            || (stage > Stage::Killers && self.killers.contains(&Some(chess_move)))
+            || (stage > Stage::CounterMove && Some(chess_move) == self.counter_move);
+        !picked && (stage == Stage::Quiets || self.moves.contains(&chess_move))
+    }
+}
+
+/// Returns the piece `chess_move` captures, including a pawn taken en passant.
+pub fn get_victim(board: &Board, chess_move: ChessMove) -> Option<Piece> {
+    match board.get_piece_at(chess_move.get_to()) {
+        Some((piece, _)) => Some(piece),
+        None if Some(chess_move.get_to()) == board.get_en_passant_square()
+            && board.get_piece_at(chess_move.get_from()).is_some_and(|(piece, _)| piece == Piece::Pawn) =>
+        {
+            Some(Piece::Pawn)
+        }
+        None => None,
+    }
+}
+
+pub fn is_quiet(board: &Board, chess_move: ChessMove) -> bool {
+    chess_move.get_promotion().is_none() && get_victim(board, chess_move).is_none()
+}
+
+// Most valuable victim first, then least valuable attacker, with promotions counted as winning the piece
+fn get_mvv_lva(board: &Board, chess_move: ChessMove) -> i32 {
+    let attacker = board.get_piece_at(chess_move.get_from()).map_or(Piece::Pawn, |(piece, _)| piece);
+    let victim = get_victim(board, chess_move).map_or(0, |victim| 10 * PIECE_VALUES[victim.to_index()]);
+    let promotion = chess_move.get_promotion().map_or(0, |piece| 10 * PIECE_VALUES[piece.to_index()]);
+    victim + promotion - PIECE_VALUES[attacker.to_index()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_core::square::*;
+
+    fn pick_all(board: &Board, mut picker: MovePicker, history: &History) -> Vec<ChessMove> {
+        std::iter::from_fn(|| picker.next(board, history)).collect()
+    }
+
+    #[test]
+    fn test_picks_every_legal_move_once() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let hash_move = ChessMove::new(E2, A6, None);
+        // A capture, an illegal move and a repeat of the hash move among the killers and countermove
+        let killers = [Some(ChessMove::new(E5, F7, None)), Some(ChessMove::new(A1, A8, None))];
+        let picker = MovePicker::new(Some(hash_move), killers, Some(hash_move), None);
+
+        let mut picked = pick_all(&board, picker, &History::new());
+        assert_eq!(picked[0], hash_move);
+        picked.sort_by_key(|chess_move| chess_move.to_u16());
+        let mut legal = board.get_legal_moves().to_vec();
+        legal.sort_by_key(|chess_move| chess_move.to_u16());
+        assert_eq!(picked, legal);
+    }
+
+    #[test]
+    fn test_stage_order() {
+        // exf5 wins a knight, exd5 trades pawns and Qxd5 loses the queen
+        let board = Board::parse_fen("4k3/8/4p3/3p1n2/4P3/8/8/3QK3 w - - 0 1").unwrap();
+        let killer = ChessMove::new(D1, H5, None);
+        let counter_move = ChessMove::new(E1, F2, None);
+        let mut history = History::new();
+        let favourite = ChessMove::new(D1, A4, None);
+        history.update_quiet(&board, favourite, &[], None, 10, 8);
+        let picker = MovePicker::new(None, [Some(killer), None], Some(counter_move), None);
+
+        let picked = pick_all(&board, picker, &history);
+        let position = |chess_move: ChessMove| picked.iter().position(|&picked| picked == chess_move).unwrap();
+        assert_eq!(picked[0], ChessMove::new(E4, F5, None));
+        assert_eq!(picked[1], ChessMove::new(E4, D5, None));
+        assert_eq!(picked[2], killer);
+        assert_eq!(picked[3], counter_move);
+        assert_eq!(picked[4], favourite);
+        assert_eq!(*picked.last().unwrap(), ChessMove::new(D1, D5, None));
+        assert!(position(ChessMove::new(D1, D2, None)) > 4);
+    }
+
+    #[test]
+    fn test_captures_only() {
+        let board = Board::parse_fen("4k3/P7/4p3/3p1n2/4P3/8/8/3QK3 w - - 0 1").unwrap();
+        let mut picker = MovePicker::new_captures();
+
+        let picked = pick_all(&board, MovePicker::new_captures(), &History::new());
+        assert_eq!(picked.len(), 6);
+        assert!(picked.iter().all(|&chess_move| !is_quiet(&board, chess_move) && board.see(chess_move) >= 0));
+        assert!(!picked.contains(&ChessMove::new(D1, D5, None)));
+
+        while picker.next(&board, &History::new()).is_some() {}
+        assert_eq!(picker.get_stage(), Stage::Done);
+    }
+}
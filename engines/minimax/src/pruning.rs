@@ -0,0 +1,179 @@
+use std::sync::OnceLock;
+
+use crate::score::Score;
+
+/// Which selective search techniques are enabled, so each one's contribution can be measured by turning
+/// it off. Everything is enabled by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pruning {
+    null_move: bool,
+    late_move_reductions: bool,
+    reverse_futility: bool,
+    futility: bool,
+    late_move_pruning: bool,
+    razoring: bool,
+}
+
+impl Pruning {
+    pub fn new() -> Self {
+        Self {
+            null_move: true,
+            late_move_reductions: true,
+            reverse_futility: true,
+            futility: true,
+            late_move_pruning: true,
+            razoring: true,
+        }
+    }
+
+    /// Disables every technique, leaving a plain alpha-beta search.
+    pub fn none() -> Self {
+        Self {
+            null_move: false,
+            late_move_reductions: false,
+            reverse_futility: false,
+            futility: false,
+            late_move_pruning: false,
+            razoring: false,
+        }
+    }
+
+    pub fn get_null_move(&self) -> bool {
+        self.null_move
+    }
+
+    pub fn set_null_move(mut self, enabled: bool) -> Self {
+        self.null_move = enabled;
+        self
+    }
+
+    pub fn get_late_move_reductions(&self) -> bool {
+        self.late_move_reductions
+    }
+
+    pub fn set_late_move_reductions(mut self, enabled: bool) -> Self {
+        self.late_move_reductions = enabled;
+        self
+    }
+
+    pub fn get_reverse_futility(&self) -> bool {
+        self.reverse_futility
+    }
+
+    pub fn set_reverse_futility(mut self, enabled: bool) -> Self {
+        self.reverse_futility = enabled;
+        self
+    }
+
+    pub fn get_futility(&self) -> bool {
+        self.futility
+    }
+
+    pub fn set_futility(mut self, enabled: bool) -> Self {
+        self.futility = enabled;
+        self
+    }
+
+    pub fn get_late_move_pruning(&self) -> bool {
+        self.late_move_pruning
+    }
+
+    pub fn set_late_move_pruning(mut self, enabled: bool) -> Self {
+        self.late_move_pruning = enabled;
+        self
+    }
+
+    pub fn get_razoring(&self) -> bool {
+        self.razoring
+    }
+
+    pub fn set_razoring(mut self, enabled: bool) -> Self {
+        self.razoring = enabled;
+        self
+    }
+}
+
+impl Default for Pruning {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The deepest remaining depth each margin based technique applies at
+pub const REVERSE_FUTILITY_DEPTH: i32 = 6;
+pub const FUTILITY_DEPTH: i32 = 5;
+pub const LATE_MOVE_PRUNING_DEPTH: i32 = 4;
+pub const RAZORING_DEPTH: i32 = 3;
+pub const NULL_MOVE_DEPTH: i32 = 3;
+
+/// How far the static evaluation must exceed beta for the node to be cut without searching.
+pub const fn get_reverse_futility_margin(depth: i32) -> Score {
+    80 * depth
+}
+
+/// How far below alpha the static evaluation must be before quiet moves are skipped.
+pub const fn get_futility_margin(depth: i32) -> Score {
+    100 + 100 * depth
+}
+
+/// How far below alpha the static evaluation must be for the node to drop into quiescence.
+pub const fn get_razoring_margin(depth: i32) -> Score {
+    250 + 200 * depth
+}
+
+/// The number of quiet moves searched before the rest are skipped.
+pub const fn get_late_move_count(depth: i32) -> usize {
+    (3 + depth * depth) as usize
+}
+
+/// The null move reduction, which grows with depth and with how far the evaluation is above beta.
+pub fn get_null_move_reduction(depth: i32, eval_margin: Score) -> i32 {
+    3 + depth / 4 + (eval_margin / 200).clamp(0, 3)
+}
+
+const LMR_SIZE: usize = 64;
+
+/// The late move reduction for the `moves`th move at `depth`, growing with the product of their logarithms.
+pub fn get_late_move_reduction(depth: i32, moves: usize) -> i32 {
+    static TABLE: OnceLock<[[i32; LMR_SIZE]; LMR_SIZE]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let mut table = [[0; LMR_SIZE]; LMR_SIZE];
+        for (depth, row) in table.iter_mut().enumerate().skip(1) {
+            for (moves, reduction) in row.iter_mut().enumerate().skip(1) {
+                *reduction = (0.75 + (depth as f64).ln() * (moves as f64).ln() / 2.25) as i32;
+            }
+        }
+        table
+    });
+    table[(depth.max(0) as usize).min(LMR_SIZE - 1)][moves.min(LMR_SIZE - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggles() {
+        let pruning = Pruning::new().set_null_move(false).set_razoring(false);
+
+        assert!(!pruning.get_null_move());
+        assert!(!pruning.get_razoring());
+        assert!(pruning.get_late_move_reductions());
+        assert!(pruning.get_futility());
+        assert_eq!(Pruning::none().set_futility(true), Pruning::new()
+            .set_null_move(false)
+            .set_late_move_reductions(false)
+            .set_reverse_futility(false)
+            .set_late_move_pruning(false)
+            .set_razoring(false));
+    }
+
+    #[test]
+    fn test_late_move_reductions_grow_with_depth_and_move_count() {
+        assert_eq!(get_late_move_reduction(1, 1), 0);
+        assert_eq!(get_late_move_reduction(0, 10), 0);
+        assert!(get_late_move_reduction(8, 20) > get_late_move_reduction(3, 4));
+        assert!(get_late_move_reduction(10, 30) >= get_late_move_reduction(10, 10));
+        assert_eq!(get_late_move_reduction(500, 500), get_late_move_reduction(63, 63));
+    }
+}
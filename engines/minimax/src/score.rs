@@ -0,0 +1,59 @@
+/// A search score in centipawns from the side to move's point of view.
+pub type Score = i32;
+
+/// The deepest ply the search reaches, which bounds the mate distances.
+pub const MAX_PLY: usize = 128;
+
+pub const DRAW: Score = 0;
+pub const MATE: Score = 32_000;
+pub const INFINITY: Score = MATE + 1;
+/// Scores at or beyond this bound are mates.
+pub const MATE_BOUND: Score = MATE - MAX_PLY as Score;
+
+/// The score for delivering mate `ply` plies from the root.
+pub const fn mate_in(ply: usize) -> Score {
+    MATE - ply as Score
+}
+
+/// The score for being mated `ply` plies from the root.
+pub const fn mated_in(ply: usize) -> Score {
+    -MATE + ply as Score
+}
+
+pub const fn is_mate_score(score: Score) -> bool {
+    score.abs() >= MATE_BOUND
+}
+
+/// Converts a mate score to the number of moves until mate as UCI reports it: positive when the side
+/// to move mates, negative when it gets mated. Returns `None` for other scores.
+pub const fn get_mate_distance(score: Score) -> Option<i32> {
+    if !is_mate_score(score) {
+        None
+    } else if score > 0 {
+        Some((MATE - score + 1) / 2)
+    } else {
+        Some(-(MATE + score) / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mate_distance() {
+        assert_eq!(get_mate_distance(mate_in(1)), Some(1));
+        assert_eq!(get_mate_distance(mate_in(3)), Some(2));
+        assert_eq!(get_mate_distance(mated_in(0)), Some(0));
+        assert_eq!(get_mate_distance(mated_in(2)), Some(-1));
+        assert_eq!(get_mate_distance(150), None);
+    }
+
+    #[test]
+    fn test_shorter_mates_score_higher() {
+        assert!(mate_in(1) > mate_in(3));
+        assert!(mated_in(4) > mated_in(2));
+        assert!(is_mate_score(mated_in(MAX_PLY)));
+        assert!(!is_mate_score(MATE_BOUND - 1));
+    }
+}
@@ -0,0 +1,1156 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use diesel_core::board::Board;
+use diesel_core::chess_move::ChessMove;
+use diesel_core::eval::{evaluate, PIECE_VALUES};
+use diesel_core::move_list::MoveList;
+use diesel_core::piece::Piece;
+use diesel_core::rank::Rank;
+
+use crate::extensions::*;
+use crate::history::{History, PieceTo};
+use crate::pruning::*;
+use crate::move_picker::{get_victim, is_quiet, MovePicker};
+use crate::score::{is_mate_score, mate_in, mated_in, Score, DRAW, INFINITY, MATE_BOUND, MAX_PLY};
+use crate::time::{TimeControl, TimeManager};
+use crate::tt::{Bound, TranspositionTable, TtEntry};
+
+pub const DEFAULT_DEPTH: i32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchLimits {
+    depth: i32,
+    nodes: Option<u64>,
+    time_control: Option<TimeControl>,
+}
+
+impl SearchLimits {
+    pub fn new() -> Self {
+        Self {
+            depth: DEFAULT_DEPTH,
+            nodes: None,
+            time_control: None,
+        }
+    }
+
+    pub fn get_depth(&self) -> i32 {
+        self.depth
+    }
+
+    /// Sets the deepest iteration to search.
+    pub fn set_depth(mut self, depth: i32) -> Self {
+        self.depth = depth.clamp(1, MAX_PLY as i32 - 1);
+        self
+    }
+
+    pub fn get_nodes(&self) -> Option<u64> {
+        self.nodes
+    }
+
+    /// Stops the search once it has visited `nodes` positions.
+    pub fn set_nodes(mut self, nodes: u64) -> Self {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    pub fn get_time_control(&self) -> Option<TimeControl> {
+        self.time_control
+    }
+
+    /// Stops the search when the clock says so. This lifts the depth limit to the maximum, so set a
+    /// depth afterwards to cap it as well.
+    pub fn set_time_control(mut self, time_control: TimeControl) -> Self {
+        self.time_control = Some(time_control);
+        self.depth = MAX_PLY as i32 - 1;
+        self
+    }
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    best_move: Option<ChessMove>,
+    score: Score,
+    pv: Vec<ChessMove>,
+    depth: i32,
+    nodes: u64,
+}
+
+impl SearchResult {
+    /// The move to play, or `None` if the side to move is mated or stalemated.
+    pub fn get_best_move(&self) -> Option<ChessMove> {
+        self.best_move
+    }
+
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    /// The principal variation, starting with the best move.
+    pub fn get_pv(&self) -> &[ChessMove] {
+        &self.pv
+    }
+
+    pub fn get_depth(&self) -> i32 {
+        self.depth
+    }
+
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+}
+
+/// A snapshot of the search after an iteration completes, in the shape UCI's `info` line expects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchInfo {
+    depth: i32,
+    sel_depth: usize,
+    nodes: u64,
+    time: Duration,
+    score: Score,
+    pv: Vec<ChessMove>,
+}
+
+impl SearchInfo {
+    pub fn get_depth(&self) -> i32 {
+        self.depth
+    }
+
+    /// The deepest ply reached during the iteration.
+    pub fn get_sel_depth(&self) -> usize {
+        self.sel_depth
+    }
+
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// The time since the search started.
+    pub fn get_time(&self) -> Duration {
+        self.time
+    }
+
+    /// Nodes per second over the whole search so far.
+    pub fn get_nps(&self) -> u64 {
+        (self.nodes as u128 * 1_000_000 / self.time.as_micros().max(1)) as u64
+    }
+
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    pub fn get_pv(&self) -> &[ChessMove] {
+        &self.pv
+    }
+}
+
+pub type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+// The first depth searched with an aspiration window, and the window's initial half-width
+const ASPIRATION_MIN_DEPTH: i32 = 4;
+const ASPIRATION_WINDOW: Score = 25;
+const TIME_CHECK_INTERVAL: u64 = 1024;
+// Slack given to a capture before delta pruning decides it can't raise alpha
+const DELTA_MARGIN: Score = 200;
+
+/// An iterative deepening negamax alpha-beta search.
+pub struct Searcher {
+    limits: SearchLimits,
+    pruning: Pruning,
+    extensions: Extensions,
+    info_callback: Option<InfoCallback>,
+    tt: Arc<TranspositionTable>,
+    threads: usize,
+    // Set by another thread to stop the search at any time. Helper threads get their own, set by the
+    // main thread once it finishes
+    stop: Arc<AtomicBool>,
+    time_manager: Option<TimeManager>,
+    // The nodes searched below each root move during this search
+    root_nodes: Vec<(ChessMove, u64)>,
+    start: Instant,
+    nodes: u64,
+    sel_depth: usize,
+    stopped: bool,
+    // Triangular PV table, where row `ply` holds the best line found from that ply
+    pv: Vec<[ChessMove; MAX_PLY]>,
+    pv_length: [usize; MAX_PLY],
+    // The previous iteration's PV, searched first while the current line still follows it
+    previous_pv: Vec<ChessMove>,
+    following_pv: bool,
+    // Hashes of the positions from the root to the current node, for repetition detection
+    hashes: Vec<u64>,
+    history: History,
+    // The piece moved and its destination at each ply on the current line
+    played: [Option<PieceTo>; MAX_PLY],
+    // Whether the move at each ply on the current line captured, for recapture extensions
+    captured: [bool; MAX_PLY],
+    // Null moves are not tried before this ply while a null move cutoff is being verified
+    null_move_min_ply: usize,
+    // The move left out at each ply while testing whether the TT move is singular
+    excluded: [Option<ChessMove>; MAX_PLY],
+    root_depth: i32,
+    root_best: Option<(ChessMove, Score)>,
+}
+
+impl Searcher {
+    pub fn new(limits: SearchLimits) -> Self {
+        Self {
+            limits,
+            pruning: Pruning::default(),
+            extensions: Extensions::default(),
+            info_callback: None,
+            tt: Arc::new(TranspositionTable::default()),
+            threads: 1,
+            stop: Arc::new(AtomicBool::new(false)),
+            time_manager: None,
+            root_nodes: Vec::new(),
+            start: Instant::now(),
+            nodes: 0,
+            sel_depth: 0,
+            stopped: false,
+            pv: vec![[ChessMove::default(); MAX_PLY]; MAX_PLY],
+            pv_length: [0; MAX_PLY],
+            previous_pv: Vec::with_capacity(MAX_PLY),
+            following_pv: false,
+            hashes: Vec::with_capacity(MAX_PLY),
+            history: History::new(),
+            played: [None; MAX_PLY],
+            captured: [false; MAX_PLY],
+            null_move_min_ply: 0,
+            excluded: [None; MAX_PLY],
+            root_depth: 0,
+            root_best: None,
+        }
+    }
+
+    pub fn get_limits(&self) -> SearchLimits {
+        self.limits
+    }
+
+    pub fn get_pruning(&self) -> Pruning {
+        self.pruning
+    }
+
+    pub fn set_pruning(mut self, pruning: Pruning) -> Self {
+        self.pruning = pruning;
+        self
+    }
+
+    pub fn get_extensions(&self) -> Extensions {
+        self.extensions
+    }
+
+    pub fn set_extensions(mut self, extensions: Extensions) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    pub fn get_transposition_table(&self) -> &Arc<TranspositionTable> {
+        &self.tt
+    }
+
+    /// Replaces the searcher's own transposition table, for example with one shared by other searchers.
+    /// This is also how to resize a shared table: build one at the new size and pass it here.
+    pub fn set_transposition_table(mut self, tt: Arc<TranspositionTable>) -> Self {
+        self.tt = tt;
+        self
+    }
+
+    pub fn get_threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Searches with `threads` threads sharing the transposition table. A single thread, the default, keeps
+    /// the search deterministic.
+    pub fn set_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the flag that stops the search when set, from any thread. The search returns the result
+    /// of the last completed iteration and leaves the flag set, so a stop sent before it starts is not lost.
+    pub fn get_stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+
+    /// Clears the stop flag. Call it before starting the next search, not from the searching thread.
+    pub fn clear_stop(&self) {
+        self.stop.store(false, Ordering::Relaxed);
+    }
+
+    /// Calls `callback` after every completed iteration.
+    pub fn set_info_callback(mut self, callback: impl FnMut(&SearchInfo) + Send + 'static) -> Self {
+        self.info_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Deepens iteratively up to the depth limit. If the node limit cuts an iteration short, the
+    /// result is that of the last completed iteration.
+    ///
+    /// With more than one thread, helper threads search the same root alongside this one, half of them
+    /// one ply deeper, and share what they find through the transposition table. Once this thread
+    /// finishes they stop, and the deepest result, then the best scoring, is returned.
+    pub fn search(&mut self, board: &Board) -> SearchResult {
+        self.tt.new_search();
+
+        if board.get_legal_moves().is_empty() {
+            let score = if board.is_in_check() { mated_in(0) } else { DRAW };
+            return SearchResult { best_move: None, score, pv: Vec::new(), depth: 0, nodes: 0 };
+        }
+        if self.threads == 1 {
+            return self.iterate(board, 0);
+        }
+
+        // Helpers stop on their own flag, set once this thread finishes, so the caller's flag is left alone
+        let helpers_stop = Arc::new(AtomicBool::new(false));
+        thread::scope(|scope| {
+            let helpers: Vec<_> = (1..self.threads)
+                .map(|id| {
+                    let mut helper = Searcher::new(SearchLimits { nodes: None, time_control: None, ..self.limits })
+                        .set_pruning(self.pruning)
+                        .set_extensions(self.extensions)
+                        .set_transposition_table(self.tt.clone());
+                    helper.stop = helpers_stop.clone();
+                    scope.spawn(move || helper.iterate(board, id as i32 % 2))
+                })
+                .collect();
+
+            let main = self.iterate(board, 0);
+            helpers_stop.store(true, Ordering::Relaxed);
+
+            let mut nodes = main.nodes;
+            let mut best = main;
+            for helper in helpers {
+                let result = helper.join().expect("search thread panicked");
+                nodes += result.nodes;
+                if result.best_move.is_some() && (result.depth, result.score) > (best.depth, best.score) {
+                    best = result;
+                }
+            }
+            SearchResult { nodes, ..best }
+        })
+    }
+
+    // Runs the iterative deepening loop on this thread, searching `depth_offset` plies deeper than the
+    // iteration number
+    fn iterate(&mut self, board: &Board, depth_offset: i32) -> SearchResult {
+        self.start = Instant::now();
+        self.nodes = 0;
+        self.stopped = false;
+        self.root_best = None;
+        self.previous_pv.clear();
+        self.hashes.clear();
+        self.hashes.push(board.get_hash());
+        self.null_move_min_ply = 0;
+        self.root_nodes.clear();
+        for ply in 0..MAX_PLY {
+            self.history.clear_killers(ply);
+        }
+        // Helpers have no clock and are stopped by the main thread
+        self.time_manager = self.limits.time_control.and_then(|time_control| TimeManager::new(time_control, board.get_to_move()));
+
+        let mut result: Option<SearchResult> = None;
+        for iteration in 1..=self.limits.depth {
+            let depth = (iteration + depth_offset).min(MAX_PLY as i32 - 1);
+            self.sel_depth = 0;
+            self.root_depth = depth;
+            let score = self.aspiration_search(board, depth, result.as_ref().map(|result| result.score));
+            if self.stopped {
+                break;
+            }
+
+            let pv = self.pv[0][..self.pv_length[0]].to_vec();
+            self.previous_pv.clone_from(&pv);
+            if let Some(callback) = &mut self.info_callback {
+                callback(&SearchInfo {
+                    depth,
+                    sel_depth: self.sel_depth,
+                    nodes: self.nodes,
+                    time: self.start.elapsed(),
+                    score,
+                    pv: pv.clone(),
+                });
+            }
+            let best_move = pv.first().copied();
+            result = Some(SearchResult { best_move, score, pv, depth, nodes: self.nodes });
+
+            let best_move_nodes = self.root_nodes.iter().find(|(chess_move, _)| Some(*chess_move) == best_move).map_or(0, |&(_, nodes)| nodes);
+            let best_move_fraction = best_move_nodes as f64 / self.nodes.max(1) as f64;
+            if self.time_manager.as_mut().is_some_and(|time_manager| time_manager.should_stop(best_move, score, best_move_fraction)) {
+                break;
+            }
+        }
+
+        match result {
+            Some(result) => SearchResult { nodes: self.nodes, ..result },
+            None => {
+                // Stopped during the first iteration, so use whatever it found or else any legal move
+                let (best_move, score) = self.root_best.unwrap_or_else(|| (board.get_legal_moves()[0], evaluate(board)));
+                SearchResult { best_move: Some(best_move), score, pv: vec![best_move], depth: 0, nodes: self.nodes }
+            }
+        }
+    }
+
+    fn add_root_nodes(&mut self, chess_move: ChessMove, nodes: u64) {
+        match self.root_nodes.iter_mut().find(|(root_move, _)| *root_move == chess_move) {
+            Some((_, total)) => *total += nodes,
+            None => self.root_nodes.push((chess_move, nodes)),
+        }
+    }
+
+    fn should_stop(&mut self) -> bool {
+        // Reading the clock is slow next to searching a node, so it is only checked now and then
+        let out_of_time = self.nodes.is_multiple_of(TIME_CHECK_INTERVAL) && self.time_manager.as_ref().is_some_and(TimeManager::is_hard_limit_reached);
+        if out_of_time || self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes) || self.stop.load(Ordering::Relaxed) {
+            self.stopped = true;
+        }
+        self.stopped
+    }
+
+    // Searches a narrow window around the previous iteration's score, widening it on the failing side
+    // until the score lands inside
+    fn aspiration_search(&mut self, board: &Board, depth: i32, previous_score: Option<Score>) -> Score {
+        let mut delta = ASPIRATION_WINDOW;
+        let (mut alpha, mut beta) = match previous_score {
+            Some(score) if depth >= ASPIRATION_MIN_DEPTH && !is_mate_score(score) => (score - delta, score + delta),
+            _ => (-INFINITY, INFINITY),
+        };
+
+        loop {
+            self.following_pv = true;
+            let score = self.negamax(board, depth, 0, alpha, beta);
+            if self.stopped {
+                return score;
+            }
+            if score <= alpha {
+                alpha = (alpha - delta).max(-INFINITY);
+            } else if score >= beta {
+                beta = (beta + delta).min(INFINITY);
+            } else {
+                return score;
+            }
+            delta *= 2;
+        }
+    }
+
+    fn negamax(&mut self, board: &Board, depth: i32, ply: usize, mut alpha: Score, mut beta: Score) -> Score {
+        if depth <= 0 {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+        self.pv_length[ply] = ply;
+        if self.should_stop() {
+            return DRAW;
+        }
+        self.nodes += 1;
+        self.sel_depth = self.sel_depth.max(ply);
+
+        let in_check = board.is_in_check();
+        if ply > 0 {
+            // Mate on the move that reaches the fifty-move limit still counts
+            let fifty_moves = board.get_halfmove_clock() >= 100 && !(in_check && board.get_legal_moves().is_empty());
+            if fifty_moves || self.is_repetition(board) {
+                return DRAW;
+            }
+            // No line from here can beat a shorter mate already found
+            alpha = alpha.max(mated_in(ply));
+            beta = beta.min(mate_in(ply + 1));
+            if alpha >= beta {
+                return alpha;
+            }
+        }
+
+        if ply >= MAX_PLY - 1 {
+            return evaluate(board);
+        }
+
+        // A deep enough entry whose bound already falls outside the window decides the node, but one
+        // inside it is searched again so the PV stays complete
+        let excluded = self.excluded[ply];
+        let tt_entry = self.tt.probe(board.get_hash(), ply);
+        if let Some(entry) = tt_entry.filter(|entry| ply > 0 && excluded.is_none() && entry.get_depth() >= depth) {
+            let score = entry.get_score();
+            if (score >= beta && entry.get_bound() != Bound::Upper) || (score <= alpha && entry.get_bound() != Bound::Lower) {
+                return score;
+            }
+        }
+        let static_eval = tt_entry.map_or_else(|| evaluate(board), |entry| entry.get_eval());
+
+        // Nodes searched with a null window only need to prove a bound, so they can be pruned
+        let pv_node = beta - alpha > 1;
+        if !pv_node && !in_check && excluded.is_none() {
+            if let Some(score) = self.prune_node(board, depth, ply, alpha, beta, static_eval) {
+                return score;
+            }
+        }
+
+        // The TT move may come from a different position with the same hash, so it is checked first
+        let pv_move = self.get_pv_move(ply);
+        let tt_move = tt_entry.and_then(|entry| entry.get_move()).filter(|&tt_move| board.is_legal(tt_move));
+        let previous = ply.checked_sub(1).and_then(|previous| self.played[previous]);
+        let mut picker = MovePicker::new(
+            pv_move.or(tt_move),
+            self.history.get_killers(ply),
+            self.history.get_counter_move(board, previous),
+            previous,
+        );
+        self.history.clear_killers(ply + 1);
+
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        let mut quiets_tried = MoveList::new();
+        let mut moves_searched = 0;
+        while let Some(chess_move) = picker.next(board, &self.history) {
+            if Some(chess_move) == excluded {
+                continue;
+            }
+            let quiet = is_quiet(board, chess_move);
+            let child = board.make_move(chess_move);
+            let gives_check = child.is_in_check();
+
+            // Quiet moves late in the list rarely raise alpha near the leaves, once something is known
+            // not to get mated
+            let prunable = !pv_node && !in_check && quiet && !gives_check && best_score > -MATE_BOUND;
+            let late = self.pruning.get_late_move_pruning()
+                && depth <= LATE_MOVE_PRUNING_DEPTH
+                && quiets_tried.len() >= get_late_move_count(depth);
+            let futile = self.pruning.get_futility()
+                && depth <= FUTILITY_DEPTH
+                && static_eval + get_futility_margin(depth) <= alpha;
+            if prunable && (late || futile) {
+                continue;
+            }
+
+            let extension = if ply < EXTENSION_PLY_FACTOR * self.root_depth as usize {
+                self.get_extension(board, chess_move, gives_check, depth, ply, tt_move.zip(tt_entry))
+            } else {
+                0
+            };
+            if self.stopped {
+                return DRAW;
+            }
+            let new_depth = depth - 1 + extension;
+
+            self.played[ply] = child.get_piece_at(chess_move.get_to()).map(|(piece, _)| (piece, chess_move.get_to()));
+            self.captured[ply] = get_victim(board, chess_move).is_some();
+            self.hashes.push(child.get_hash());
+            let nodes_before = self.nodes;
+            let score = if moves_searched == 0 {
+                -self.negamax(&child, new_depth, ply + 1, -beta, -alpha)
+            } else {
+                // Later moves are expected to fail low, so they are searched with a null window, reduced
+                // when quiet, and searched again only when they turn out better than expected
+                let mut reduction = 0;
+                if self.pruning.get_late_move_reductions() && depth >= 3 && quiet && !in_check && !gives_check {
+                    reduction = get_late_move_reduction(depth, moves_searched + 1) - pv_node as i32;
+                    reduction = reduction.clamp(0, depth - 2);
+                }
+                let mut score = -self.negamax(&child, new_depth - reduction, ply + 1, -alpha - 1, -alpha);
+                if score > alpha && reduction > 0 {
+                    score = -self.negamax(&child, new_depth, ply + 1, -alpha - 1, -alpha);
+                }
+                if score > alpha && score < beta {
+                    score = -self.negamax(&child, new_depth, ply + 1, -beta, -alpha);
+                }
+                score
+            };
+            self.hashes.pop();
+            moves_searched += 1;
+            if ply == 0 {
+                self.add_root_nodes(chess_move, self.nodes - nodes_before);
+            }
+            // Only the first move can continue the previous PV
+            self.following_pv = false;
+
+            if self.stopped {
+                return DRAW;
+            }
+            if score > best_score {
+                best_score = score;
+                if ply == 0 {
+                    self.root_best = Some((chess_move, score));
+                }
+                if score > alpha {
+                    alpha = score;
+                    best_move = Some(chess_move);
+                    self.update_pv(ply, chess_move);
+                    if alpha >= beta {
+                        if quiet {
+                            self.history.update_quiet(board, chess_move, &quiets_tried, previous, ply, depth);
+                        }
+                        break;
+                    }
+                }
+            }
+            if quiet {
+                quiets_tried.push(chess_move);
+            }
+        }
+
+        if best_score == -INFINITY {
+            return match excluded {
+                // Every other move was pruned or there are none, so the excluded move is singular
+                Some(_) => alpha,
+                None if in_check => mated_in(ply),
+                None => DRAW,
+            };
+        }
+
+        let bound = if best_score >= beta {
+            Bound::Lower
+        } else if best_score > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        if excluded.is_none() {
+            self.tt.store(board.get_hash(), ply, TtEntry::new(best_move, best_score, static_eval, depth, bound));
+        }
+        best_score
+    }
+
+    // Returns how many plies to extend `chess_move` by, at most one. The TT move is singular when a
+    // reduced search of every other move fails below its score by a margin.
+    fn get_extension(&mut self, board: &Board, chess_move: ChessMove, gives_check: bool, depth: i32, ply: usize, tt: Option<(ChessMove, TtEntry)>) -> i32 {
+        let us = board.get_to_move();
+        let previous = ply.checked_sub(1).and_then(|previous| self.played[previous]);
+        let recapture = ply > 0
+            && self.captured[ply - 1]
+            && previous.is_some_and(|(_, to)| to == chess_move.get_to())
+            && get_victim(board, chess_move).is_some();
+        let passed_pawn = chess_move.get_to().relative_rank(us) == Rank::Seventh
+            && board.get_piece_at(chess_move.get_from()).is_some_and(|(piece, _)| piece == Piece::Pawn);
+        if (self.extensions.get_check() && gives_check)
+            || (self.extensions.get_recapture() && recapture)
+            || (self.extensions.get_passed_pawn() && passed_pawn)
+        {
+            return 1;
+        }
+
+        let Some((tt_move, entry)) = tt else {
+            return 0;
+        };
+        let singular_candidate = self.extensions.get_singular()
+            && ply > 0
+            && depth >= SINGULAR_DEPTH
+            && chess_move == tt_move
+            && self.excluded[ply].is_none()
+            && entry.get_depth() >= depth - SINGULAR_TT_DEPTH_MARGIN
+            && entry.get_bound() != Bound::Upper
+            && !is_mate_score(entry.get_score());
+        if !singular_candidate {
+            return 0;
+        }
+
+        let singular_beta = get_singular_beta(entry.get_score(), depth);
+        // The PV move is the one excluded, so the moves searched instead must not follow the PV. The
+        // search shares this ply's PV line, which may already hold a line from an earlier move
+        let following_pv = self.following_pv;
+        let pv = self.pv[ply];
+        let pv_length = self.pv_length[ply];
+        self.following_pv = false;
+        self.excluded[ply] = Some(chess_move);
+        let score = self.negamax(board, (depth - 1) / 2, ply, singular_beta - 1, singular_beta);
+        self.excluded[ply] = None;
+        self.following_pv = following_pv;
+        self.pv[ply] = pv;
+        self.pv_length[ply] = pv_length;
+        (score < singular_beta) as i32
+    }
+
+    // Tries to decide a non-PV node that isn't in check before searching its moves: reverse futility
+    // pruning when the evaluation is far above beta, razoring when it is far below alpha, and null move
+    // pruning when passing still fails high
+    fn prune_node(&mut self, board: &Board, depth: i32, ply: usize, alpha: Score, beta: Score, static_eval: Score) -> Option<Score> {
+        if self.pruning.get_reverse_futility()
+            && depth <= REVERSE_FUTILITY_DEPTH
+            && !is_mate_score(beta)
+            && static_eval - get_reverse_futility_margin(depth) >= beta
+        {
+            return Some(static_eval);
+        }
+
+        if self.pruning.get_razoring() && depth <= RAZORING_DEPTH && static_eval + get_razoring_margin(depth) <= alpha {
+            let score = self.quiescence(board, ply, alpha, alpha + 1);
+            if score <= alpha {
+                return Some(score);
+            }
+        }
+
+        // Without pieces other than pawns, passing may be the best move, so null moves would be unsound
+        let us = board.get_to_move();
+        let pieces = board.get_pieces_bitboard(us) - board.get_pawn_bitboard(us) - board.get_king_bitboard(us);
+        let after_null_move = ply > 0 && self.played[ply - 1].is_none();
+        if self.pruning.get_null_move()
+            && depth >= NULL_MOVE_DEPTH
+            && ply >= self.null_move_min_ply
+            && !after_null_move
+            && !pieces.is_empty()
+            && static_eval >= beta
+            && !is_mate_score(beta)
+        {
+            let reduction = get_null_move_reduction(depth, static_eval - beta);
+            let child = board.make_null_move();
+            self.played[ply] = None;
+            self.captured[ply] = false;
+            self.hashes.push(child.get_hash());
+            let score = -self.negamax(&child, depth - 1 - reduction, ply + 1, -beta, -beta + 1);
+            self.hashes.pop();
+            if self.stopped {
+                return Some(DRAW);
+            }
+
+            if score >= beta {
+                // A mate found after passing isn't proven, so only the bound is returned
+                let score = if is_mate_score(score) { beta } else { score };
+                // With few pieces zugzwang is likely, so the cutoff is verified by a reduced search
+                // without null moves near this node
+                if pieces.count_bits() > 2 {
+                    return Some(score);
+                }
+                // Restored afterwards rather than cleared, as this may be nested in another verification
+                let null_move_min_ply = self.null_move_min_ply;
+                self.null_move_min_ply = ply + (3 * (depth - reduction) / 4) as usize;
+                let verified = self.negamax(board, depth - reduction, ply, beta - 1, beta);
+                self.null_move_min_ply = null_move_min_ply;
+                if verified >= beta {
+                    return Some(score);
+                }
+            }
+        }
+        None
+    }
+
+    // Searches captures and promotions until the position is quiet, so the static evaluation is never
+    // taken in the middle of an exchange. In check every evasion is searched instead.
+    fn quiescence(&mut self, board: &Board, ply: usize, mut alpha: Score, beta: Score) -> Score {
+        self.pv_length[ply] = ply;
+        if self.should_stop() {
+            return DRAW;
+        }
+        self.nodes += 1;
+        self.sel_depth = self.sel_depth.max(ply);
+
+        if ply >= MAX_PLY - 1 {
+            return evaluate(board);
+        }
+
+        let in_check = board.is_in_check();
+        // Standing pat assumes the side to move can do at least as well as the static evaluation by
+        // declining every capture, which only holds when it isn't in check
+        let stand_pat = evaluate(board);
+        let mut best_score = if in_check { -INFINITY } else { stand_pat };
+        if !in_check {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        // Losing captures never come out of the picker, which is the SEE pruning
+        let mut picker = if in_check { MovePicker::new(None, [None; 2], None, None) } else { MovePicker::new_captures() };
+        while let Some(chess_move) = picker.next(board, &self.history) {
+            if !in_check {
+                let victim = get_victim(board, chess_move).map_or(0, |piece| PIECE_VALUES[piece.to_index()]);
+                let promotion = chess_move
+                    .get_promotion()
+                    .map_or(0, |piece| PIECE_VALUES[piece.to_index()] - PIECE_VALUES[Piece::Pawn.to_index()]);
+                if stand_pat + victim + promotion + DELTA_MARGIN <= alpha {
+                    continue;
+                }
+            }
+
+            let score = -self.quiescence(&board.make_move(chess_move), ply + 1, -beta, -alpha);
+            if self.stopped {
+                return DRAW;
+            }
+            if score > best_score {
+                best_score = score;
+                if score > alpha {
+                    alpha = score;
+                    self.update_pv(ply, chess_move);
+                    if alpha >= beta {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if in_check && best_score == -INFINITY {
+            return mated_in(ply);
+        }
+        best_score
+    }
+
+    // While the line so far matches the previous PV, its next move is legal here
+    fn get_pv_move(&mut self, ply: usize) -> Option<ChessMove> {
+        if !self.following_pv {
+            return None;
+        }
+        let pv_move = self.previous_pv.get(ply).copied();
+        self.following_pv = pv_move.is_some();
+        pv_move
+    }
+
+    fn update_pv(&mut self, ply: usize, chess_move: ChessMove) {
+        let child_length = self.pv_length[ply + 1];
+        let (parent, child) = self.pv.split_at_mut(ply + 1);
+        let line = &mut parent[ply];
+        line[ply] = chess_move;
+        line[ply + 1..child_length].copy_from_slice(&child[0][ply + 1..child_length]);
+        self.pv_length[ply] = child_length;
+    }
+
+    // A position repeated since the last capture or pawn move is scored as a draw, as the side that
+    // could avoid the repetition would already have done so
+    fn is_repetition(&self, board: &Board) -> bool {
+        let hash = board.get_hash();
+        self.hashes
+            .iter()
+            .rev()
+            .take(board.get_halfmove_clock() as usize + 1)
+            .skip(2)
+            .step_by(2)
+            .any(|&previous| previous == hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_core::square::*;
+    use crate::score::get_mate_distance;
+
+    fn search(fen: &str, limits: SearchLimits) -> SearchResult {
+        Searcher::new(limits).search(&Board::parse_fen(fen).unwrap())
+    }
+
+    #[test]
+    fn test_finds_mate_in_one() {
+        let result = search("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", SearchLimits::new().set_depth(2));
+
+        assert_eq!(result.get_best_move().map(|m| m.to_string()), Some("a1a8".to_string()));
+        assert_eq!(result.get_score(), mate_in(1));
+        assert_eq!(get_mate_distance(result.get_score()), Some(1));
+    }
+
+    #[test]
+    fn test_finds_mate_in_two() {
+        let result = search("k7/8/2K5/8/8/8/8/7R w - - 0 1", SearchLimits::new().set_depth(4));
+
+        assert_eq!(get_mate_distance(result.get_score()), Some(2));
+        // Quiescence may extend the line past the nominal depth
+        assert!(result.get_pv().len() >= 3);
+    }
+
+    #[test]
+    fn test_captures_hanging_queen() {
+        let result = search("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", SearchLimits::new().set_depth(3));
+
+        assert_eq!(result.get_best_move().map(|m| m.to_string()), Some("d2d5".to_string()));
+        assert!(result.get_score() > 0);
+    }
+
+    #[test]
+    fn test_checkmated_and_stalemated_positions() {
+        let mated = search("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1", SearchLimits::new());
+        let stalemated = search("7k/5Q2/8/8/8/8/8/6K1 b - - 0 1", SearchLimits::new());
+
+        assert_eq!(mated.get_best_move(), None);
+        assert_eq!(mated.get_score(), mated_in(0));
+        assert_eq!(stalemated.get_best_move(), None);
+        assert_eq!(stalemated.get_score(), DRAW);
+    }
+
+    #[test]
+    fn test_pv_is_playable() {
+        let board = Board::default();
+        let result = Searcher::new(SearchLimits::new().set_depth(3)).search(&board);
+
+        // Quiescence may extend the line past the nominal depth
+        assert!(result.get_pv().len() >= 3);
+        result.get_pv().iter().fold(board, |board, &chess_move| {
+            assert!(board.is_legal(chess_move));
+            board.make_move(chess_move)
+        });
+    }
+
+    #[test]
+    fn test_reports_every_iteration() {
+        let infos = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let reported = infos.clone();
+        let mut searcher = Searcher::new(SearchLimits::new().set_depth(5))
+            .set_info_callback(move |info| reported.lock().unwrap().push(info.clone()));
+
+        let result = searcher.search(&Board::default());
+        let infos = infos.lock().unwrap();
+
+        assert_eq!(infos.iter().map(SearchInfo::get_depth).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert!(infos.windows(2).all(|pair| pair[0].get_nodes() < pair[1].get_nodes()));
+        assert!(infos.iter().all(|info| info.get_sel_depth() >= info.get_depth() as usize));
+        let last = infos.last().unwrap();
+        assert_eq!(last.get_pv(), result.get_pv());
+        assert_eq!(last.get_score(), result.get_score());
+    }
+
+    #[test]
+    fn test_aspiration_windows_keep_the_exact_score() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let board = Board::parse_fen(fen).unwrap();
+        let mut searcher = Searcher::new(SearchLimits::new().set_depth(4));
+
+        let deepened = searcher.search(&board);
+        // A single full-window search at the same depth
+        searcher.previous_pv.clear();
+        searcher.following_pv = false;
+        let full_window = searcher.negamax(&board, 4, 0, -INFINITY, INFINITY);
+
+        assert_eq!(deepened.get_score(), full_window);
+    }
+
+    #[test]
+    fn test_quiescence_sees_the_recapture() {
+        // At depth 1 the queen would grab the pawn without quiescence and miss the recapture
+        let result = search("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1", SearchLimits::new().set_depth(1));
+
+        assert_ne!(result.get_best_move().map(|m| m.to_string()), Some("d1d5".to_string()));
+        // Up a queen for two pawns, give or take the positional terms
+        assert!((600..800).contains(&result.get_score()));
+    }
+
+    #[test]
+    fn test_quiescence_searches_evasions_in_check() {
+        let mut searcher = Searcher::new(SearchLimits::new());
+        let mated = Board::parse_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        let escapes = Board::parse_fen("R5k1/6pp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+
+        assert_eq!(searcher.quiescence(&mated, 1, -INFINITY, INFINITY), mated_in(1));
+        assert!(!is_mate_score(searcher.quiescence(&escapes, 1, -INFINITY, INFINITY)));
+    }
+
+    #[test]
+    fn test_transposition_table_is_reused() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let mut searcher = Searcher::new(SearchLimits::new().set_depth(4));
+
+        let first = searcher.search(&board);
+        assert!(searcher.get_transposition_table().probe(board.get_hash(), 0).is_some());
+        let second = searcher.search(&board);
+        assert!(second.get_nodes() < first.get_nodes());
+        assert_eq!(second.get_best_move(), first.get_best_move());
+    }
+
+    #[test]
+    fn test_shared_transposition_table() {
+        let tt = Arc::new(TranspositionTable::new(1));
+        let board = Board::default();
+        Searcher::new(SearchLimits::new().set_depth(3)).set_transposition_table(tt.clone()).search(&board);
+
+        assert!(tt.probe(board.get_hash(), 0).is_some_and(|entry| entry.get_depth() == 3));
+    }
+
+    #[test]
+    fn test_resize_shared_transposition_table() {
+        let board = Board::default();
+        let mut tt = Arc::new(TranspositionTable::new(1));
+        let mut searcher = Searcher::new(SearchLimits::new().set_depth(3)).set_transposition_table(tt.clone());
+        searcher.search(&board);
+
+        let mut searcher = searcher.set_transposition_table(Arc::new(TranspositionTable::new(2)));
+        assert_eq!(searcher.get_transposition_table().get_size_mb(), 2);
+        assert!(searcher.get_transposition_table().probe(board.get_hash(), 0).is_none());
+        searcher.search(&board);
+        assert!(searcher.get_transposition_table().probe(board.get_hash(), 0).is_some());
+
+        // No searcher holds the old table any more, so it can be resized in place
+        Arc::get_mut(&mut tt).unwrap().resize(4);
+        assert_eq!(tt.get_size_mb(), 4);
+    }
+
+    #[test]
+    fn test_pruning_searches_fewer_nodes() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let limits = SearchLimits::new().set_depth(5);
+
+        let pruned = Searcher::new(limits).search(&board);
+        let full = Searcher::new(limits).set_pruning(Pruning::none()).search(&board);
+        assert!(pruned.get_nodes() < full.get_nodes());
+    }
+
+    #[test]
+    fn test_each_pruning_technique_keeps_mates() {
+        let techniques = [
+            Pruning::none().set_null_move(true),
+            Pruning::none().set_late_move_reductions(true),
+            Pruning::none().set_reverse_futility(true),
+            Pruning::none().set_futility(true),
+            Pruning::none().set_late_move_pruning(true),
+            Pruning::none().set_razoring(true),
+            Pruning::new(),
+        ];
+        for pruning in techniques {
+            let board = Board::parse_fen("k7/8/2K5/8/8/8/8/7R w - - 0 1").unwrap();
+            let result = Searcher::new(SearchLimits::new().set_depth(5)).set_pruning(pruning).search(&board);
+
+            assert_eq!(get_mate_distance(result.get_score()), Some(2), "{:?}", pruning);
+        }
+    }
+
+    #[test]
+    fn test_extensions() {
+        let mut searcher = Searcher::new(SearchLimits::new()).set_extensions(Extensions::new().set_recapture(true).set_passed_pawn(true));
+        let board = Board::parse_fen("4k3/1P6/8/3p4/4P3/8/8/4K2R w - - 0 1").unwrap();
+        let extension = |searcher: &mut Searcher, uci: &str| {
+            let chess_move: ChessMove = uci.parse().unwrap();
+            let gives_check = board.make_move(chess_move).is_in_check();
+            searcher.get_extension(&board, chess_move, gives_check, 1, 1, None)
+        };
+
+        assert_eq!(extension(&mut searcher, "h1h8"), 1);
+        assert_eq!(extension(&mut searcher, "h1h7"), 0);
+        searcher.played[0] = Some((Piece::Pawn, D5));
+        assert_eq!(extension(&mut searcher, "e4d5"), 0);
+        searcher.captured[0] = true;
+        assert_eq!(extension(&mut searcher, "e4d5"), 1);
+
+        let board = Board::parse_fen("4k3/8/1P6/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let chess_move = ChessMove::new(B6, B7, None);
+        assert_eq!(searcher.get_extension(&board, chess_move, false, 1, 1, None), 1);
+        let mut searcher = searcher.set_extensions(Extensions::none());
+        assert_eq!(searcher.get_extension(&board, chess_move, false, 1, 1, None), 0);
+    }
+
+    #[test]
+    fn test_singular_extension() {
+        let mut searcher = Searcher::new(SearchLimits::new());
+        let mut singular = |fen: &str, uci: &str, score: Score| {
+            let board = Board::parse_fen(fen).unwrap();
+            let chess_move: ChessMove = uci.parse().unwrap();
+            let entry = TtEntry::new(Some(chess_move), score, score, SINGULAR_DEPTH, Bound::Exact);
+            searcher.get_extension(&board, chess_move, false, SINGULAR_DEPTH, 1, Some((chess_move, entry)))
+        };
+
+        // Only taking the queen keeps the material, while every opening move is about as good as e4
+        assert_eq!(singular("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", "d2d5", 500), 1);
+        assert_eq!(singular("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "e2e4", 0), 0);
+    }
+
+    #[test]
+    fn test_check_extension_finds_mate_behind_a_check() {
+        // Qg8+ Rxg8 Nf7# is only seen at depth 2 when the checks extend the line
+        let board = Board::parse_fen("5r1k/6pp/7N/8/8/1Q6/8/6K1 w - - 0 1").unwrap();
+        let limits = SearchLimits::new().set_depth(2);
+        // Razoring would drop the quiet mate at the last ply, so only the extensions are compared
+        let extended = Searcher::new(limits).set_pruning(Pruning::none()).search(&board);
+        let unextended = Searcher::new(limits).set_pruning(Pruning::none()).set_extensions(Extensions::none()).search(&board);
+
+        assert_eq!(get_mate_distance(extended.get_score()), Some(2));
+        assert_eq!(extended.get_best_move().map(|m| m.to_string()), Some("b3g8".to_string()));
+        assert_eq!(get_mate_distance(unextended.get_score()), None);
+    }
+
+    #[test]
+    fn test_multiple_threads() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let limits = SearchLimits::new().set_depth(4);
+        let mut searcher = Searcher::new(limits).set_threads(4);
+
+        let result = searcher.search(&board);
+        assert_eq!(searcher.get_threads(), 4);
+        assert!(result.get_depth() >= 4);
+        assert!(board.is_legal(result.get_best_move().unwrap()));
+
+        let mated = Searcher::new(limits).set_threads(4).search(&Board::parse_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap());
+        assert_eq!(mated.get_best_move(), None);
+        let mate = Searcher::new(limits).set_threads(3).search(&Board::parse_fen("k7/8/2K5/8/8/8/8/7R w - - 0 1").unwrap());
+        assert_eq!(get_mate_distance(mate.get_score()), Some(2));
+    }
+
+    #[test]
+    fn test_single_thread_is_deterministic() {
+        let board = Board::parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let limits = SearchLimits::new().set_depth(4);
+        let first = Searcher::new(limits).set_threads(0).search(&board);
+        let second = Searcher::new(limits).search(&board);
+
+        assert_eq!(first.get_nodes(), second.get_nodes());
+        assert_eq!(first.get_pv(), second.get_pv());
+        assert_eq!(first.get_score(), second.get_score());
+    }
+
+    #[test]
+    fn test_time_control() {
+        let board = Board::default();
+        let limits = SearchLimits::new();
+        let start = Instant::now();
+        let move_time = Searcher::new(limits.set_time_control(TimeControl::new().set_move_time(Duration::from_millis(200)))).search(&board);
+
+        assert!(start.elapsed() < Duration::from_millis(1_000));
+        assert!(move_time.get_depth() >= 1);
+        assert!(move_time.get_best_move().is_some());
+
+        let start = Instant::now();
+        let clock = TimeControl::new().set_white_time(Duration::from_millis(3_000)).set_black_time(Duration::from_millis(10));
+        let result = Searcher::new(limits.set_time_control(clock)).set_threads(2).search(&board);
+        assert!(start.elapsed() < Duration::from_millis(1_000));
+        assert!(result.get_best_move().is_some());
+    }
+
+    #[test]
+    fn test_time_control_lifts_depth_limit() {
+        let clock = TimeControl::new().set_move_time(Duration::from_millis(300));
+        let board = Board::parse_fen("8/8/8/4k3/8/8/8/R3K3 w - - 0 1").unwrap();
+
+        assert_eq!(SearchLimits::new().set_time_control(clock).get_depth(), MAX_PLY as i32 - 1);
+        assert_eq!(SearchLimits::new().set_time_control(clock).set_depth(6).get_depth(), 6);
+        assert!(Searcher::new(SearchLimits::new().set_time_control(clock)).search(&board).get_depth() > DEFAULT_DEPTH);
+    }
+
+    #[test]
+    fn test_stop_from_another_thread() {
+        let mut searcher = Searcher::new(SearchLimits::new().set_depth(MAX_PLY as i32 - 1));
+        let stop = searcher.get_stop_flag();
+        let start = Instant::now();
+        let handle = std::thread::spawn(move || searcher.search(&Board::default()));
+
+        std::thread::sleep(Duration::from_millis(100));
+        stop.store(true, Ordering::Relaxed);
+        let result = handle.join().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(2_000));
+        assert!(result.get_best_move().is_some());
+    }
+
+    #[test]
+    fn test_stop_before_search() {
+        let mut searcher = Searcher::new(SearchLimits::new().set_depth(3)).set_threads(2);
+        searcher.get_stop_flag().store(true, Ordering::Relaxed);
+        let result = searcher.search(&Board::default());
+
+        assert_eq!(result.get_depth(), 0);
+        assert!(result.get_best_move().is_some());
+        assert!(searcher.get_stop_flag().load(Ordering::Relaxed));
+
+        searcher.clear_stop();
+        assert_eq!(searcher.search(&Board::default()).get_depth(), 3);
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let result = search(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            SearchLimits::new().set_depth(10).set_nodes(2_000),
+        );
+
+        assert_eq!(result.get_nodes(), 2_000);
+        assert!(result.get_best_move().is_some());
+        assert!(result.get_depth() < 10);
+    }
+}
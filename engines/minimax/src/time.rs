@@ -0,0 +1,260 @@
+use std::time::{Duration, Instant};
+
+use diesel_core::chess_move::ChessMove;
+use diesel_core::color::Color;
+
+use crate::score::Score;
+
+// Kept in reserve on every move for communication and scheduling delays
+const MOVE_OVERHEAD: Duration = Duration::from_millis(10);
+// The number of moves the remaining time is spread over when the time control doesn't say
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+const MAX_MOVES_TO_GO: u32 = 50;
+// How many times the soft limit the hard limit allows, and the most of the clock either may use
+const HARD_LIMIT_FACTOR: u32 = 4;
+const MAX_CLOCK_FRACTION: f64 = 0.8;
+// Time scales for a best move that has been the same for 0, 1, 2, 3 and 4 or more iterations
+const STABILITY_FACTORS: [f64; 5] = [2.0, 1.4, 1.1, 0.9, 0.8];
+// A score drop of this many centipawns or more gets the most extra time
+const SCORE_DROP_SCALE: f64 = 100.0;
+const MAX_SCORE_DROP_FACTOR: f64 = 1.5;
+// Time scales with this base less the best move's share of the nodes, times the scale, so the less
+// effort the best move took the more time is given. A share of about three quarters leaves it unchanged
+const NODE_FRACTION_BASE: f64 = 1.5;
+const NODE_FRACTION_SCALE: f64 = 1.35;
+
+/// The clock state a search starts with, as given by the UCI `go` command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeControl {
+    white_time: Option<Duration>,
+    black_time: Option<Duration>,
+    white_increment: Duration,
+    black_increment: Duration,
+    moves_to_go: Option<u32>,
+    move_time: Option<Duration>,
+}
+
+impl TimeControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_white_time(&self) -> Option<Duration> {
+        self.white_time
+    }
+
+    pub fn set_white_time(mut self, time: Duration) -> Self {
+        self.white_time = Some(time);
+        self
+    }
+
+    pub fn get_black_time(&self) -> Option<Duration> {
+        self.black_time
+    }
+
+    pub fn set_black_time(mut self, time: Duration) -> Self {
+        self.black_time = Some(time);
+        self
+    }
+
+    pub fn get_white_increment(&self) -> Duration {
+        self.white_increment
+    }
+
+    pub fn set_white_increment(mut self, increment: Duration) -> Self {
+        self.white_increment = increment;
+        self
+    }
+
+    pub fn get_black_increment(&self) -> Duration {
+        self.black_increment
+    }
+
+    pub fn set_black_increment(mut self, increment: Duration) -> Self {
+        self.black_increment = increment;
+        self
+    }
+
+    pub fn get_moves_to_go(&self) -> Option<u32> {
+        self.moves_to_go
+    }
+
+    /// Sets the number of moves until the next time control.
+    pub fn set_moves_to_go(mut self, moves: u32) -> Self {
+        self.moves_to_go = Some(moves);
+        self
+    }
+
+    pub fn get_move_time(&self) -> Option<Duration> {
+        self.move_time
+    }
+
+    /// Searches for exactly `time`, overriding the clocks.
+    pub fn set_move_time(mut self, time: Duration) -> Self {
+        self.move_time = Some(time);
+        self
+    }
+
+    pub fn get_time(&self, color: Color) -> Option<Duration> {
+        match color {
+            Color::White => self.white_time,
+            Color::Black => self.black_time,
+        }
+    }
+
+    pub fn get_increment(&self, color: Color) -> Duration {
+        match color {
+            Color::White => self.white_increment,
+            Color::Black => self.black_increment,
+        }
+    }
+}
+
+/// Decides when to stop a search. The hard limit is never exceeded, while the soft limit is checked
+/// between iterations and scaled by how settled the search looks: a best move that keeps changing, a
+/// falling score or little effort spent on the best move all earn more time.
+#[derive(Clone, Debug)]
+pub struct TimeManager {
+    start: Instant,
+    soft_limit: Duration,
+    hard_limit: Duration,
+    fixed: bool,
+    previous_best_move: Option<ChessMove>,
+    previous_score: Option<Score>,
+    stability: usize,
+}
+
+impl TimeManager {
+    /// Returns `None` when the time control sets no limit for `color`.
+    pub fn new(time_control: TimeControl, color: Color) -> Option<Self> {
+        let (soft_limit, hard_limit, fixed) = match time_control.move_time {
+            Some(move_time) => {
+                let limit = move_time.saturating_sub(MOVE_OVERHEAD);
+                (limit, limit, true)
+            }
+            None => {
+                let time = time_control.get_time(color)?;
+                let increment = time_control.get_increment(color);
+                let moves_to_go = time_control.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).clamp(1, MAX_MOVES_TO_GO);
+
+                let usable = time.saturating_sub(MOVE_OVERHEAD);
+                let max_time = usable.mul_f64(MAX_CLOCK_FRACTION);
+                let soft_limit = (usable / moves_to_go + increment * 3 / 4).min(max_time);
+                let hard_limit = (soft_limit * HARD_LIMIT_FACTOR).min(max_time);
+                (soft_limit, hard_limit, false)
+            }
+        };
+        Some(Self {
+            start: Instant::now(),
+            soft_limit,
+            hard_limit,
+            fixed,
+            previous_best_move: None,
+            previous_score: None,
+            stability: 0,
+        })
+    }
+
+    pub fn get_soft_limit(&self) -> Duration {
+        self.soft_limit
+    }
+
+    pub fn get_hard_limit(&self) -> Duration {
+        self.hard_limit
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn is_hard_limit_reached(&self) -> bool {
+        self.get_elapsed() >= self.hard_limit
+    }
+
+    /// Returns the soft limit scaled by the search's progress, never beyond the hard limit.
+    pub fn get_adjusted_soft_limit(&self, score: Score, best_move_fraction: f64) -> Duration {
+        if self.fixed {
+            return self.soft_limit;
+        }
+        let stability = STABILITY_FACTORS[self.stability.min(STABILITY_FACTORS.len() - 1)];
+        let score_drop = self.previous_score.map_or(0, |previous| previous - score).max(0) as f64;
+        let score_factor = 1.0 + (score_drop / SCORE_DROP_SCALE).min(1.0) * (MAX_SCORE_DROP_FACTOR - 1.0);
+        let node_factor = (NODE_FRACTION_BASE - best_move_fraction.clamp(0.0, 1.0)) * NODE_FRACTION_SCALE;
+        self.soft_limit.mul_f64(stability * score_factor * node_factor).min(self.hard_limit)
+    }
+
+    /// Records a completed iteration and returns `true` if another one shouldn't be started.
+    /// `best_move_fraction` is the share of the search's nodes spent below the best move.
+    pub fn should_stop(&mut self, best_move: Option<ChessMove>, score: Score, best_move_fraction: f64) -> bool {
+        if self.previous_best_move.is_some() && best_move == self.previous_best_move {
+            self.stability += 1;
+        } else {
+            self.stability = 0;
+        }
+        let limit = self.get_adjusted_soft_limit(score, best_move_fraction);
+        self.previous_best_move = best_move;
+        self.previous_score = Some(score);
+        self.get_elapsed() >= limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_core::square::*;
+
+    fn clock(time: u64, increment: u64) -> TimeControl {
+        TimeControl::new()
+            .set_white_time(Duration::from_millis(time))
+            .set_white_increment(Duration::from_millis(increment))
+            .set_black_time(Duration::from_millis(1_000))
+    }
+
+    #[test]
+    fn test_limits() {
+        let manager = TimeManager::new(clock(60_010, 1_000), Color::White).unwrap();
+        assert_eq!(manager.get_soft_limit(), Duration::from_millis(2_000 + 750));
+        assert_eq!(manager.get_hard_limit(), Duration::from_millis(11_000));
+
+        let black = TimeManager::new(clock(60_010, 1_000), Color::Black).unwrap();
+        assert!(black.get_soft_limit() < manager.get_soft_limit());
+        assert!(black.get_hard_limit() <= Duration::from_millis(990).mul_f64(MAX_CLOCK_FRACTION));
+
+        let sudden_death = TimeManager::new(clock(1_010, 5_000), Color::White).unwrap();
+        assert_eq!(sudden_death.get_hard_limit(), Duration::from_millis(800));
+        assert_eq!(sudden_death.get_soft_limit(), Duration::from_millis(800));
+
+        let moves_to_go = TimeManager::new(clock(10_010, 0).set_moves_to_go(5), Color::White).unwrap();
+        assert_eq!(moves_to_go.get_soft_limit(), Duration::from_millis(2_000));
+    }
+
+    #[test]
+    fn test_move_time_and_no_clock() {
+        let manager = TimeManager::new(TimeControl::new().set_move_time(Duration::from_millis(500)), Color::Black).unwrap();
+        assert_eq!(manager.get_soft_limit(), Duration::from_millis(490));
+        assert_eq!(manager.get_hard_limit(), Duration::from_millis(490));
+        assert_eq!(manager.get_adjusted_soft_limit(-500, 0.0), Duration::from_millis(490));
+
+        assert!(TimeManager::new(TimeControl::new(), Color::White).is_none());
+        assert!(TimeManager::new(TimeControl::new().set_black_time(Duration::from_secs(1)), Color::White).is_none());
+    }
+
+    #[test]
+    fn test_adjustments() {
+        let mut manager = TimeManager::new(clock(60_010, 0), Color::White).unwrap();
+        let best_move = Some(ChessMove::new(E2, E4, None));
+        let unsettled = manager.get_adjusted_soft_limit(0, 0.5);
+
+        for _ in 0..5 {
+            manager.should_stop(best_move, 0, 0.5);
+        }
+        let stable = manager.get_adjusted_soft_limit(0, 0.5);
+        assert!(stable < unsettled);
+        assert!(manager.get_adjusted_soft_limit(-80, 0.5) > stable);
+        assert!(manager.get_adjusted_soft_limit(0, 0.9) < stable);
+        assert!(manager.get_adjusted_soft_limit(-1_000, 0.0) <= manager.get_hard_limit());
+
+        manager.should_stop(Some(ChessMove::new(D2, D4, None)), 0, 0.5);
+        assert_eq!(manager.get_adjusted_soft_limit(0, 0.5), unsettled);
+    }
+}
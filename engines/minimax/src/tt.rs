@@ -0,0 +1,383 @@
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use diesel_core::chess_move::ChessMove;
+
+use crate::score::{Score, MATE_BOUND};
+
+pub const DEFAULT_HASH_MB: usize = 16;
+
+// Entries sharing an index, sized so a cluster fills one 64 byte cache line
+const CLUSTER_SIZE: usize = 4;
+// The generation is stored in the 6 bits above the bound
+const GENERATION_BITS: u8 = 6;
+const GENERATION_MASK: u8 = (1 << GENERATION_BITS) - 1;
+// How many plies of depth one generation of age is worth when choosing an entry to replace
+const AGE_WEIGHT: i32 = 8;
+
+/// How a stored score relates to the true score of the position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    /// The true score is at most the stored score, as every move failed low.
+    Upper,
+    /// The true score is at least the stored score, as a move failed high.
+    Lower,
+    Exact,
+}
+
+impl Bound {
+    const fn to_bits(self) -> u8 {
+        match self {
+            Bound::Upper => 1,
+            Bound::Lower => 2,
+            Bound::Exact => 3,
+        }
+    }
+
+    const fn from_bits(bits: u8) -> Option<Bound> {
+        match bits & 3 {
+            1 => Some(Bound::Upper),
+            2 => Some(Bound::Lower),
+            3 => Some(Bound::Exact),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TtEntry {
+    chess_move: Option<ChessMove>,
+    score: Score,
+    eval: Score,
+    depth: i32,
+    bound: Bound,
+}
+
+impl TtEntry {
+    pub fn new(chess_move: Option<ChessMove>, score: Score, eval: Score, depth: i32, bound: Bound) -> Self {
+        Self { chess_move, score, eval, depth, bound }
+    }
+
+    pub fn get_move(&self) -> Option<ChessMove> {
+        self.chess_move
+    }
+
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    pub fn get_eval(&self) -> Score {
+        self.eval
+    }
+
+    pub fn get_depth(&self) -> i32 {
+        self.depth
+    }
+
+    pub fn get_bound(&self) -> Bound {
+        self.bound
+    }
+}
+
+// The data is packed as move | score << 16 | eval << 32 | depth << 48 | (bound | generation << 2) << 56.
+// The key is stored xored with the data, so an entry torn by two threads writing at once fails to match
+// instead of returning one position's data for another.
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+impl Slot {
+    fn load(&self) -> (u64, u64) {
+        let data = self.data.load(Ordering::Relaxed);
+        (self.key.load(Ordering::Relaxed) ^ data, data)
+    }
+
+    fn store(&self, hash: u64, data: u64) {
+        self.key.store(hash ^ data, Ordering::Relaxed);
+        self.data.store(data, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+#[repr(align(64))]
+struct Cluster {
+    slots: [Slot; CLUSTER_SIZE],
+}
+
+const fn get_data_move(data: u64) -> u16 {
+    data as u16
+}
+
+const fn get_data_depth(data: u64) -> i32 {
+    (data >> 48) as u8 as i32
+}
+
+const fn get_data_flags(data: u64) -> u8 {
+    (data >> 56) as u8
+}
+
+/// A transposition table shared lock free between search threads, holding the results of earlier searches
+/// by Zobrist hash.
+pub struct TranspositionTable {
+    clusters: Box<[Cluster]>,
+    generation: AtomicU8,
+}
+
+impl TranspositionTable {
+    /// Allocates a table of about `megabytes` MB, with at least one cluster.
+    pub fn new(megabytes: usize) -> Self {
+        let count = (megabytes * 1024 * 1024 / size_of::<Cluster>()).max(1);
+        Self {
+            clusters: (0..count).map(|_| Cluster::default()).collect(),
+            generation: AtomicU8::new(0),
+        }
+    }
+
+    /// Reallocates the table at a new size, discarding every entry. A table shared through an `Arc` can
+    /// only be resized here once it is the last reference (see `Arc::get_mut`). Otherwise build a new
+    /// table between searches and hand it to `Searcher::set_transposition_table`.
+    pub fn resize(&mut self, megabytes: usize) {
+        *self = Self::new(megabytes);
+    }
+
+    pub fn get_size_mb(&self) -> usize {
+        self.clusters.len() * size_of::<Cluster>() / (1024 * 1024)
+    }
+
+    pub fn clear(&self) {
+        for slot in self.clusters.iter().flat_map(|cluster| &cluster.slots) {
+            slot.key.store(0, Ordering::Relaxed);
+            slot.data.store(0, Ordering::Relaxed);
+        }
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    /// Ages every entry by one generation, so entries from earlier searches are replaced first.
+    pub fn new_search(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get_generation(&self) -> u8 {
+        self.generation.load(Ordering::Relaxed) & GENERATION_MASK
+    }
+
+    // Maps the hash onto the clusters by multiplying instead of taking a modulo, so any size works
+    fn get_cluster(&self, hash: u64) -> &Cluster {
+        let index = (hash as u128 * self.clusters.len() as u128) >> 64;
+        &self.clusters[index as usize]
+    }
+
+    /// Returns the entry stored for `hash`, with mate scores made relative to `ply`.
+    pub fn probe(&self, hash: u64, ply: usize) -> Option<TtEntry> {
+        self.get_cluster(hash).slots.iter().find_map(|slot| {
+            let (key, data) = slot.load();
+            let bound = Bound::from_bits(get_data_flags(data))?;
+            (key == hash).then(|| TtEntry {
+                chess_move: Some(get_data_move(data)).filter(|&bits| bits != 0).and_then(ChessMove::from_u16),
+                score: score_from_tt((data >> 16) as i16 as Score, ply),
+                eval: (data >> 32) as i16 as Score,
+                depth: get_data_depth(data),
+                bound,
+            })
+        })
+    }
+
+    /// Stores the result of searching `hash` at `ply`. An existing entry for the position keeps its move
+    /// when the new one has none, and keeps everything when it is deeper and not being replaced by an
+    /// exact score. Otherwise the shallowest and oldest entry in the cluster is replaced.
+    pub fn store(&self, hash: u64, ply: usize, entry: TtEntry) {
+        let TtEntry { chess_move, score, eval, depth, bound } = entry;
+        let generation = self.get_generation();
+        let cluster = self.get_cluster(hash);
+
+        let mut replace = &cluster.slots[0];
+        let mut replace_worth = i32::MAX;
+        let mut existing = None;
+        for slot in &cluster.slots {
+            let (key, data) = slot.load();
+            let flags = get_data_flags(data);
+            let occupied = Bound::from_bits(flags).is_some();
+            if occupied && key == hash {
+                replace = slot;
+                existing = Some(data);
+                break;
+            }
+            let age = (generation.wrapping_sub(flags >> 2) & GENERATION_MASK) as i32;
+            let worth = if occupied { get_data_depth(data) - AGE_WEIGHT * age } else { i32::MIN };
+            if worth < replace_worth {
+                replace = slot;
+                replace_worth = worth;
+            }
+        }
+
+        let mut packed_move = chess_move.map_or(0, |chess_move| chess_move.to_u16());
+        if let Some(data) = existing {
+            if bound != Bound::Exact && get_data_depth(data) > depth + 2 && get_data_flags(data) >> 2 == generation {
+                return;
+            }
+            if packed_move == 0 {
+                packed_move = get_data_move(data);
+            }
+        }
+
+        let data = packed_move as u64
+            | (score_to_tt(score, ply) as i16 as u16 as u64) << 16
+            | (eval as i16 as u16 as u64) << 32
+            | (depth.clamp(0, u8::MAX as i32) as u64) << 48
+            | ((bound.to_bits() | generation << 2) as u64) << 56;
+        replace.store(hash, data);
+    }
+
+    /// Returns how full the table is in permille, counting entries from the current search in a sample.
+    pub fn get_hashfull(&self) -> usize {
+        let generation = self.get_generation();
+        self.clusters
+            .iter()
+            .take(1000)
+            .flat_map(|cluster| &cluster.slots)
+            .filter(|slot| {
+                let flags = get_data_flags(slot.data.load(Ordering::Relaxed));
+                Bound::from_bits(flags).is_some() && flags >> 2 == generation
+            })
+            .count()
+            * 1000
+            / (self.clusters.len().min(1000) * CLUSTER_SIZE)
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_MB)
+    }
+}
+
+// Mate scores are stored relative to the position rather than the root, as the same position can be
+// reached at different plies
+fn score_to_tt(score: Score, ply: usize) -> Score {
+    if score >= MATE_BOUND {
+        score + ply as Score
+    } else if score <= -MATE_BOUND {
+        score - ply as Score
+    } else {
+        score
+    }
+}
+
+fn score_from_tt(score: Score, ply: usize) -> Score {
+    if score >= MATE_BOUND {
+        score - ply as Score
+    } else if score <= -MATE_BOUND {
+        score + ply as Score
+    } else {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score::{mate_in, mated_in};
+    use diesel_core::piece::Piece;
+    use diesel_core::square::*;
+
+    // Spreads small test keys over the table the way Zobrist hashes would be
+    fn mix(key: u64) -> u64 {
+        key.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    #[test]
+    fn test_store_and_probe() {
+        let tt = TranspositionTable::new(1);
+        let chess_move = ChessMove::new(E7, E8, Some(Piece::Queen));
+        tt.store(0xDEAD_BEEF, 3, TtEntry::new(Some(chess_move), -150, 42, 7, Bound::Lower));
+
+        let entry = tt.probe(0xDEAD_BEEF, 3).unwrap();
+        assert_eq!(entry.get_move(), Some(chess_move));
+        assert_eq!(entry.get_score(), -150);
+        assert_eq!(entry.get_eval(), 42);
+        assert_eq!(entry.get_depth(), 7);
+        assert_eq!(entry.get_bound(), Bound::Lower);
+        assert_eq!(tt.probe(0xBEEF_DEAD, 3), None);
+    }
+
+    #[test]
+    fn test_mate_scores_are_relative_to_the_position() {
+        let tt = TranspositionTable::new(1);
+        // Mate in 3 plies from a position at ply 2 is mate in 5 from the root
+        tt.store(1, 2, TtEntry::new(None, mate_in(5), 0, 4, Bound::Exact));
+        tt.store(2, 2, TtEntry::new(None, mated_in(6), 0, 4, Bound::Exact));
+
+        assert_eq!(tt.probe(1, 6).unwrap().get_score(), mate_in(9));
+        assert_eq!(tt.probe(2, 0).unwrap().get_score(), mated_in(4));
+    }
+
+    #[test]
+    fn test_keeps_the_move_when_storing_without_one() {
+        let tt = TranspositionTable::new(1);
+        let chess_move = ChessMove::new(G1, F3, None);
+        tt.store(7, 0, TtEntry::new(Some(chess_move), 10, 0, 3, Bound::Lower));
+        tt.store(7, 0, TtEntry::new(None, -20, 0, 4, Bound::Upper));
+
+        let entry = tt.probe(7, 0).unwrap();
+        assert_eq!(entry.get_move(), Some(chess_move));
+        assert_eq!(entry.get_score(), -20);
+    }
+
+    #[test]
+    fn test_replaces_the_oldest_shallowest_entry() {
+        // A single cluster, so every hash collides
+        let tt = TranspositionTable::new(0);
+        for hash in 1..=CLUSTER_SIZE as u64 {
+            tt.store(hash, 0, TtEntry::new(None, 0, 0, 10 + hash as i32, Bound::Exact));
+        }
+        tt.store(100, 0, TtEntry::new(None, 0, 0, 1, Bound::Exact));
+        assert_eq!(tt.probe(1, 0), None);
+
+        // After a few searches the deepest entry is old enough to go before a fresh shallow one
+        for _ in 0..3 {
+            tt.new_search();
+        }
+        tt.store(200, 0, TtEntry::new(None, 0, 0, 5, Bound::Exact));
+        tt.store(300, 0, TtEntry::new(None, 0, 0, 5, Bound::Exact));
+        assert_eq!(tt.probe(100, 0), None);
+        assert_eq!(tt.probe(2, 0), None);
+        assert!(tt.probe(200, 0).is_some());
+        assert!(tt.probe(3, 0).is_some());
+    }
+
+    #[test]
+    fn test_clear_and_hashfull() {
+        let tt = TranspositionTable::new(1);
+        assert_eq!(tt.get_size_mb(), 1);
+        for hash in 0..10_000u64 {
+            tt.store(mix(hash), 0, TtEntry::new(None, 0, 0, 1, Bound::Exact));
+        }
+        assert!(tt.get_hashfull() > 0);
+
+        tt.clear();
+        assert_eq!(tt.get_hashfull(), 0);
+        assert_eq!(tt.probe(0, 0), None);
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let tt = std::sync::Arc::new(TranspositionTable::new(1));
+        let handles: Vec<_> = (0..4u64)
+            .map(|thread| {
+                let tt = tt.clone();
+                std::thread::spawn(move || {
+                    for hash in 0..1_000u64 {
+                        tt.store(mix(hash << 8 | thread), 0, TtEntry::new(None, thread as Score, 0, 1, Bound::Exact));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(tt.probe(mix(999 << 8 | 3), 0).map(|entry| entry.get_score()), Some(3));
+    }
+}